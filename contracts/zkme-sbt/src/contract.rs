@@ -34,6 +34,15 @@ pub fn instantiate(
         state::grant_role(deps.storage, role, info.sender.clone())?;
     }
 
+    // Init Ownership
+    state::set_ownership(
+        deps.storage,
+        &state::Ownership {
+            owner: Some(info.sender.clone()),
+            ..state::Ownership::default()
+        },
+    )?;
+
     // Init Counter
     state::initialize_counter(deps.storage)?;
 
@@ -51,13 +60,68 @@ pub fn execute(
     match msg {
         GrantRole { role, user } => exec::grant_role(deps, env, info, &role, user),
         RevokeRole { role, user } => exec::revoke_role(deps, env, info, &role, user),
+        UpdateOwnership(action) => exec::update_ownership(deps, env, info, action),
+        SetPauseFlags { flags } => exec::set_pause_flags(deps, env, info, flags),
 
         SetQuestions {
             cooperator,
             questions,
         } => exec::set_questions(deps, env, info, cooperator, questions),
 
+        SetPolicy { cooperator, policy } => exec::set_policy(deps, env, info, cooperator, policy),
+
+        SetTiers { tiers } => exec::set_tiers(deps, env, info, tiers),
+        SetTier { token_id, tier } => exec::set_tier(deps, env, info, token_id, tier),
+        SetJurisdiction {
+            token_id,
+            jurisdiction,
+        } => exec::set_jurisdiction(deps, env, info, token_id, jurisdiction),
+        SetAgeClaims { token_id, over } => exec::set_age_claims(deps, env, info, token_id, over),
+
+        RegisterIssuer {
+            issuer,
+            name,
+            trust_level,
+        } => exec::register_issuer(deps, env, info, issuer, name, trust_level),
+        RemoveIssuer { issuer } => exec::remove_issuer(deps, env, info, issuer),
+        IssueAttestation {
+            token_id,
+            validity,
+            data,
+            questions,
+        } => exec::issue_attestation(deps, env, info, token_id, validity, data, questions),
+        WithdrawAttestation { token_id } => exec::withdraw_attestation(deps, env, info, token_id),
+
         Attest { to } => exec::attest(deps, env, info, to),
+        RecoverToken {
+            token_id,
+            new_owner,
+            move_approvals,
+        } => exec::recover_token(deps, env, info, token_id, new_owner, move_approvals),
+        SetGuardians {
+            guardians,
+            threshold,
+        } => exec::set_guardians(deps, env, info, guardians, threshold),
+        ProposeRecovery {
+            token_id,
+            new_owner,
+        } => exec::propose_recovery(deps, env, info, token_id, new_owner),
+        ApproveRecovery { token_id } => exec::approve_recovery(deps, env, info, token_id),
+        CancelRecovery { token_id } => exec::cancel_recovery(deps, env, info, token_id),
+        ExecuteRecovery { token_id } => exec::execute_recovery(deps, env, info, token_id),
+        SetRecoveryDelay { seconds } => exec::set_recovery_delay(deps, env, info, seconds),
+        SetGuardianLimit { limit } => exec::set_guardian_limit(deps, env, info, limit),
+        LinkAddress {
+            address,
+            pubkey,
+            signature,
+        } => exec::link_address(deps, env, info, address, pubkey, signature),
+        UnlinkAddress { address } => exec::unlink_address(deps, env, info, address),
+        SetLinkLimit { limit } => exec::set_link_limit(deps, env, info, limit),
+        RegisterEvmAddress { pubkey, signature } => {
+            exec::register_evm_address(deps, env, info, pubkey, signature)
+        }
+        EraseMyData { keep_token } => exec::erase_my_data(deps, env, info, keep_token),
         Burn {
             from: _,
             token_id: _,
@@ -69,7 +133,43 @@ pub fn execute(
             data,
             questions,
         } => exec::set_kyc_data(deps, env, info, token_id, key, validity, data, questions),
+        SetKycCommitment {
+            token_id,
+            key,
+            validity,
+            questions,
+            commitment,
+        } => exec::set_kyc_commitment(
+            deps, env, info, token_id, key, validity, questions, commitment,
+        ),
+        RenewKyc { token_id, validity } => exec::renew_kyc(deps, env, info, token_id, validity),
+        SetGracePeriod { seconds } => exec::set_grace_period(deps, env, info, seconds),
+        SetAttributeRoot { token_id, root } => {
+            exec::set_attribute_root(deps, env, info, token_id, root)
+        }
         SetTokenBaseUri { uri } => exec::set_token_base_uri(deps, env, info, uri),
+        SetTimelockDelay { seconds } => exec::set_timelock_delay(deps, env, info, seconds),
+        ExecuteAction { id } => exec::execute_action(deps, env, info, id),
+        CancelAction { id } => exec::cancel_action(deps, env, info, id),
+        AddAttestorKey {
+            key_id,
+            algorithm,
+            pubkey,
+        } => exec::add_attestor_key(deps, env, info, key_id, algorithm, pubkey),
+        RevokeAttestorKey { key_id } => exec::revoke_attestor_key(deps, env, info, key_id),
+        SubmitAttestation {
+            attestation,
+            signature,
+        } => exec::submit_attestation(deps, env, info, attestation, signature),
+        RegisterVerifyingKey { question, vk } => {
+            exec::register_verifying_key(deps, env, info, question, vk)
+        }
+        SubmitProof {
+            token_id,
+            question,
+            proof,
+            public_inputs,
+        } => exec::submit_proof(deps, env, info, token_id, question, proof, public_inputs),
         Approve {
             cooperator,
             token_id,
@@ -79,6 +179,31 @@ pub fn execute(
             cooperator,
             token_id,
         } => exec::revoke(deps, env, info, cooperator, token_id),
+        ApproveMany { approvals } => exec::approve_many(deps, env, info, approvals),
+        RevokeAll {} => exec::revoke_all(deps, env, info),
+        SetBulkLimit { limit } => exec::set_bulk_limit(deps, env, info, limit),
+        PruneExpired { limit } => exec::prune_expired(deps, env, info, limit),
+        SetRetentionPeriod { seconds } => exec::set_retention_period(deps, env, info, seconds),
+        Backfill { index, limit } => exec::backfill(deps, env, info, index, limit),
+        GrantDelegate {
+            delegate,
+            expires_at,
+            cooperators,
+        } => exec::grant_delegate(deps, env, info, delegate, expires_at, cooperators),
+        RevokeDelegate { delegate } => exec::revoke_delegate(deps, env, info, delegate),
+        FreezeToken { token_id, reason } => exec::freeze_token(deps, env, info, token_id, reason),
+        UnfreezeToken { token_id, reason } => {
+            exec::unfreeze_token(deps, env, info, token_id, reason)
+        }
+        InspectToken { token_id, reason } => exec::inspect_token(deps, env, info, token_id, reason),
+        AddToDenylist { address, reason } => {
+            exec::add_to_denylist(deps, env, info, address, reason)
+        }
+        RemoveFromDenylist { address } => exec::remove_from_denylist(deps, env, info, address),
+        SetScreeningContract { contract } => {
+            exec::set_screening_contract(deps, env, info, contract)
+        }
+        RevokePermit { permit_name } => exec::revoke_permit(deps, env, info, permit_name),
     }
 }
 
@@ -88,22 +213,120 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
 
     match msg {
         IsRole { role, user } => Ok(to_binary(&qry::is_role(deps, env, &role, user)?)?),
+        Ownership {} => Ok(to_binary(&qry::ownership(deps, env)?)?),
+        PauseFlags {} => Ok(to_binary(&qry::pause_flags(deps, env)?)?),
         GetQuestions { cooperator } => Ok(to_binary(&qry::get_questions(deps, env, cooperator)?)?),
-        GetKycData { token_id } => Ok(to_binary(&qry::get_kyc_data(deps, env, token_id)?)?),
+        GetPolicy { cooperator } => Ok(to_binary(&qry::get_policy(deps, env, cooperator)?)?),
+        ListTiers {} => Ok(to_binary(&qry::list_tiers(deps, env)?)?),
+        GetTier { user } => Ok(to_binary(&qry::get_tier(deps, env, user)?)?),
+        ListIssuers { start_after, limit } => Ok(to_binary(&qry::list_issuers(
+            deps,
+            env,
+            start_after,
+            limit,
+        )?)?),
+        ListAttestations { token_id, permit } => Ok(to_binary(&qry::list_attestations(
+            deps, env, token_id, permit,
+        )?)?),
+        GetKycData { token_id, permit } => {
+            Ok(to_binary(&qry::get_kyc_data(deps, env, token_id, permit)?)?)
+        }
+        ListExpiring {
+            before,
+            start_after,
+            limit,
+        } => Ok(to_binary(&qry::list_expiring(
+            deps,
+            env,
+            before,
+            start_after,
+            limit,
+        )?)?),
+        GracePeriod {} => Ok(to_binary(&qry::grace_period(deps, env)?)?),
+        GetErasure { user } => Ok(to_binary(&qry::get_erasure(deps, env, user)?)?),
+        GetRecovery { account } => Ok(to_binary(&qry::get_recovery(deps, env, account)?)?),
+        GetGuardians { token_id } => Ok(to_binary(&qry::get_guardians(deps, env, token_id)?)?),
+        RecoveryDelay {} => Ok(to_binary(&qry::recovery_delay(deps, env)?)?),
+        GuardianLimit {} => Ok(to_binary(&qry::guardian_limit(deps, env)?)?),
+        ListLinkedAddresses { token_id } => Ok(to_binary(&qry::list_linked_addresses(
+            deps, env, token_id,
+        )?)?),
+        LinkLimit {} => Ok(to_binary(&qry::link_limit(deps, env)?)?),
+        GetEvmMapping { evm_address } => {
+            Ok(to_binary(&qry::get_evm_mapping(deps, env, evm_address)?)?)
+        }
+        DeriveAddresses { pubkey } => Ok(to_binary(&qry::derive_addresses(deps, env, pubkey)?)?),
+        VerifyEvm {
+            cooperator,
+            evm_address,
+        } => Ok(to_binary(&qry::verify_evm(
+            deps,
+            env,
+            cooperator,
+            evm_address,
+        )?)?),
+        HasApprovedEvm {
+            cooperator,
+            evm_address,
+        } => Ok(to_binary(&qry::has_approved_evm(
+            deps,
+            env,
+            cooperator,
+            evm_address,
+        )?)?),
+        RetentionPeriod {} => Ok(to_binary(&qry::retention_period(deps, env)?)?),
+        GetKycCommitment { token_id } => {
+            Ok(to_binary(&qry::get_kyc_commitment(deps, env, token_id)?)?)
+        }
+        VerifyField {
+            token_id,
+            field,
+            value,
+            salt,
+        } => Ok(to_binary(&qry::verify_field(
+            deps, env, token_id, field, value, salt,
+        )?)?),
+        VerifyAttributeProof {
+            token_id,
+            leaf,
+            proof,
+        } => Ok(to_binary(&qry::verify_attribute_proof(
+            deps, env, token_id, leaf, proof,
+        )?)?),
+        GetVerifyingKey { question } => {
+            Ok(to_binary(&qry::get_verifying_key(deps, env, question)?)?)
+        }
+        GetVerifiedClaim { token_id, question } => Ok(to_binary(&qry::get_verified_claim(
+            deps, env, token_id, question,
+        )?)?),
+        ListAttestorKeys { start_after, limit } => Ok(to_binary(&qry::list_attestor_keys(
+            deps,
+            env,
+            start_after,
+            limit,
+        )?)?),
+        GetAttestationNonce { token_id } => Ok(to_binary(&qry::get_attestation_nonce(
+            deps, env, token_id,
+        )?)?),
         BalanceOf { owner } => Ok(to_binary(&qry::balance_of(deps, env, owner)?)?),
         TokenIdOf { from } => Ok(to_binary(&qry::token_id_of(deps, env, from)?)?),
         OwnerOf { token_id } => Ok(to_binary(&qry::owner_of(deps, env, token_id)?)?),
         TotalSupply {} | NumTokens {} => Ok(to_binary(&qry::total_supply(deps, env)?)?),
         TokenUri { token_id } => Ok(to_binary(&qry::token_uri(deps, env, token_id)?)?),
         Verify { cooperator, user } => Ok(to_binary(&qry::verify(deps, env, cooperator, user)?)?),
+        IsOverAge { user, age } => Ok(to_binary(&qry::is_over_age(deps, env, user, age)?)?),
         HasApproved { cooperator, user } => {
             Ok(to_binary(&qry::has_approved(deps, env, cooperator, user)?)?)
         }
         GetUserTokenId { cooperator, user } => Ok(to_binary(&qry::get_user_token_id(
             deps, env, cooperator, user,
         )?)?),
-        GetUserData { cooperator, user } => Ok(to_binary(&qry::get_user_data(
-            deps, env, cooperator, user,
+        GetUserData {
+            cooperator,
+            user,
+            permit,
+        } => Ok(to_binary(&qry::get_user_data(
+            deps, env, cooperator, user, permit,
         )?)?),
         GetApprovedTokenId {
             cooperator,
@@ -115,30 +338,400 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
         GetApprovedLength { cooperator } => Ok(to_binary(&qry::get_approved_length(
             deps, env, cooperator,
         )?)?),
-        GetApprovedUserKycData { cooperator, user } => Ok(to_binary(
-            &qry::get_approved_user_kyc_data(deps, env, cooperator, user)?,
-        )?),
+        GetApprovedUserKycData {
+            cooperator,
+            user,
+            permit,
+        } => Ok(to_binary(&qry::get_approved_user_kyc_data(
+            deps, env, cooperator, user, permit,
+        )?)?),
+        BulkLimit {} => Ok(to_binary(&qry::bulk_limit(deps, env)?)?),
+        ListApprovalsByUser {
+            user,
+            start_after,
+            limit,
+        } => Ok(to_binary(&qry::list_approvals_by_user(
+            deps,
+            env,
+            user,
+            start_after,
+            limit,
+        )?)?),
+        GetDelegate { owner, delegate } => {
+            Ok(to_binary(&qry::get_delegate(deps, env, owner, delegate)?)?)
+        }
+        ListDelegates {
+            owner,
+            start_after,
+            limit,
+        } => Ok(to_binary(&qry::list_delegates(
+            deps,
+            env,
+            owner,
+            start_after,
+            limit,
+        )?)?),
+        GetFreezeStatus { token_id } => {
+            Ok(to_binary(&qry::get_freeze_status(deps, env, token_id)?)?)
+        }
+        GetFreezeHistory { token_id } => {
+            Ok(to_binary(&qry::get_freeze_history(deps, env, token_id)?)?)
+        }
+        GetScreening { address } => Ok(to_binary(&qry::get_screening(deps, env, address)?)?),
+        ListDenylist { start_after, limit } => Ok(to_binary(&qry::list_denylist(
+            deps,
+            env,
+            start_after,
+            limit,
+        )?)?),
+        ListAuditLog { start_after, limit } => Ok(to_binary(&qry::list_audit_log(
+            deps,
+            env,
+            start_after,
+            limit,
+        )?)?),
+        PendingActions { start_after, limit } => Ok(to_binary(&qry::pending_actions(
+            deps,
+            env,
+            start_after,
+            limit,
+        )?)?),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
+    // Contracts instantiated before ownership existed are owned by their first admin
+    if !state::has_ownership(deps.storage) {
+        let owner = state::first_admin(deps.storage)?;
+        state::set_ownership(
+            deps.storage,
+            &state::Ownership {
+                owner,
+                ..state::Ownership::default()
+            },
+        )?;
+    }
+    // Indexes added since are filled in by paged `ExecuteMsg::Backfill` calls
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("version", CONTRACT_VERSION);
+    Ok(resp)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        contract, msg,
-        state::{COOPERATOR_ROLE, DEFAULT_ADMIN_ROLE},
+        contract, crypto,
+        error::ContractError,
+        msg::{self, Permit, PermitParams},
+        state::{
+            self, BackfillIndex, FieldCommitment, FreezeAction, IssuerRule, JurisdictionRule,
+            KeyAlgorithm, KycCommitment, KycData, Ownership, PauseFlags, QueuedAction,
+            TimelockAction, VerifiedClaim, VerifyPolicy, COOPERATOR_ROLE, DEFAULT_ADMIN_ROLE,
+            INSPECTOR_ROLE, OPERATOR_ROLE, PAUSER_ROLE,
+        },
+        verifier,
     };
-    use cosmwasm_std::Addr;
-    use cosmwasm_std::{from_binary, Empty};
-    use cosmwasm_std::{
-        testing::{mock_dependencies, mock_env, mock_info},
-        Timestamp,
+    use ark_bn254::{Bn254, Fr};
+    use ark_groth16::{Groth16, VerifyingKey};
+    use ark_relations::{
+        lc,
+        r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError},
     };
-    use cw_multi_test::{App, ContractWrapper, Executor};
+    use ark_serialize::CanonicalSerialize;
+    use ark_snark::SNARK;
+    use ark_std::rand::{rngs::StdRng, SeedableRng};
+    use bech32::{ToBase32, Variant};
+    use cosmwasm_std::{from_binary, to_vec, Addr, Binary, Empty, Timestamp};
+    use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
+    use cw_utils::Expiration;
+    use k256::{
+        ecdsa::{signature::Signer, Signature, SigningKey},
+        elliptic_curve::sec1::ToEncodedPoint,
+    };
+
+    fn setup(deployer: &Addr) -> (App, Addr) {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(contract::execute, contract::instantiate, contract::query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(code_id, deployer.clone(), &Empty {}, &[], "zkMeSBT", None)
+            .unwrap();
+
+        (app, addr)
+    }
+
+    fn keypair(seed: u8) -> (SigningKey, Addr) {
+        let key = SigningKey::from_bytes(&[seed; 32]).unwrap();
+        let pubkey = key.verifying_key().to_bytes();
+        let account = crypto::pubkey_to_account(&pubkey);
+        let addr = bech32::encode("sei", account.to_base32(), Variant::Bech32).unwrap();
+        (key, Addr::unchecked(addr))
+    }
+
+    fn sign_permit(app: &App, addr: &Addr, key: &SigningKey, signer: &Addr, name: &str) -> Permit {
+        let params = PermitParams {
+            permit_name: name.to_string(),
+            chain_id: app.block_info().chain_id,
+            contract: addr.clone(),
+            expires_at: None,
+        };
+        let signature: Signature = key.sign(&crypto::adr036_sign_bytes(
+            signer,
+            &to_vec(&params).unwrap(),
+        ));
+        Permit {
+            params,
+            signer: signer.clone(),
+            pubkey: Binary::from(key.verifying_key().to_bytes().as_slice()),
+            signature: Binary::from(signature.as_ref()),
+        }
+    }
+
+    fn attest_with_kyc(app: &mut App, addr: &Addr, operator: &Addr, user: &Addr) -> u64 {
+        app.execute_contract(
+            user.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::Attest { to: user.clone() },
+            &[],
+        )
+        .unwrap();
+
+        let resp: msg::TokenIdOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::TokenIdOf { from: user.clone() },
+            )
+            .unwrap();
+
+        app.execute_contract(
+            operator.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetKycData {
+                token_id: resp.token_id,
+                key: "key".to_string(),
+                validity: app.block_info().time.plus_days(30),
+                data: "data".to_string(),
+                questions: vec!["question".to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        resp.token_id
+    }
+
+    fn grant_cooperator(app: &mut App, addr: &Addr, admin: &Addr, cooperator: &Addr) {
+        app.execute_contract(
+            admin.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::GrantRole {
+                role: COOPERATOR_ROLE.to_string(),
+                user: cooperator.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn set_questions(app: &mut App, addr: &Addr, operator: &Addr, cooperator: &Addr) {
+        app.execute_contract(
+            operator.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetQuestions {
+                cooperator: cooperator.clone(),
+                questions: vec!["question".to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn verify(app: &App, addr: &Addr, cooperator: &Addr, user: &Addr) -> msg::VerifyResponse {
+        app.wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::Verify {
+                    cooperator: cooperator.clone(),
+                    user: user.clone(),
+                },
+            )
+            .unwrap()
+    }
+
+    fn approve(app: &mut App, addr: &Addr, user: &Addr, cooperator: &Addr, token_id: u64) {
+        app.execute_contract(
+            user.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::Approve {
+                cooperator: cooperator.clone(),
+                token_id,
+                cooperator_key: "cooperator_key".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn link_address(
+        app: &mut App,
+        addr: &Addr,
+        owner: &Addr,
+        key: &SigningKey,
+        address: &Addr,
+        token_id: u64,
+    ) -> Result<AppResponse, ContractError> {
+        let params = msg::LinkParams {
+            chain_id: app.block_info().chain_id,
+            contract: addr.clone(),
+            token_id,
+            address: address.clone(),
+        };
+        let signature: Signature = key.sign(&crypto::adr036_sign_bytes(
+            address,
+            &to_vec(&params).unwrap(),
+        ));
+        app.execute_contract(
+            owner.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::LinkAddress {
+                address: address.clone(),
+                pubkey: Binary::from(key.verifying_key().to_bytes().as_slice()),
+                signature: Binary::from(signature.as_ref()),
+            },
+            &[],
+        )
+        .map_err(|err| err.downcast::<ContractError>().unwrap())
+    }
+
+    #[test]
+    fn test_migrate_and_backfill() {
+        use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+        use cosmwasm_std::OwnedDeps;
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let carol = Addr::unchecked("carol");
+
+        // approvals stored by a contract predating the reverse index
+        state::save_pu(deps.as_mut().storage, &bob, &alice, 1).unwrap();
+        state::save_pu(deps.as_mut().storage, &carol, &alice, 1).unwrap();
+        state::save_approval(
+            deps.as_mut().storage,
+            &carol,
+            &alice,
+            1,
+            Timestamp::from_seconds(1),
+        )
+        .unwrap();
+        // kyc data and its cooperator copy stored before either index
+        let validity = Timestamp::from_seconds(1_000);
+        let kyc = KycData {
+            key: "key".to_string(),
+            validity,
+            data: "data".to_string(),
+            questions: vec!["question".to_string()],
+        };
+        state::KYC_MAP
+            .save(deps.as_mut().storage, &1, &kyc)
+            .unwrap();
+        state::APPROVED_KYC_MAP
+            .save(deps.as_mut().storage, (&bob, 1), &kyc)
+            .unwrap();
+
+        let resp = contract::migrate(deps.as_mut(), env.clone(), Empty {}).unwrap();
+        assert_eq!(resp.attributes[1].value, super::CONTRACT_VERSION);
+        assert_eq!(
+            cw2::get_contract_version(deps.as_ref().storage)
+                .unwrap()
+                .version,
+            super::CONTRACT_VERSION
+        );
+
+        // backfills page through each index, for admins only
+        let backfill = |deps: &mut OwnedDeps<_, _, _>, index: BackfillIndex, limit: u32| {
+            let resp = contract::execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info("admin", &[]),
+                msg::ExecuteMsg::Backfill { index, limit },
+            )?;
+            let value = |key: &str| {
+                resp.attributes
+                    .iter()
+                    .find(|attr| attr.key == key)
+                    .unwrap()
+                    .value
+                    .clone()
+            };
+            Ok::<_, ContractError>((value("added"), value("remaining")))
+        };
+        assert_eq!(
+            backfill(&mut deps, BackfillIndex::Approvals, 1).unwrap_err(),
+            ContractError::InvalidAdminAccount {
+                account: Addr::unchecked("admin")
+            }
+        );
+        state::grant_role(
+            deps.as_mut().storage,
+            DEFAULT_ADMIN_ROLE,
+            Addr::unchecked("admin"),
+        )
+        .unwrap();
+        let page = |added: &str, remaining: bool| (added.to_string(), remaining.to_string());
+        assert_eq!(
+            backfill(&mut deps, BackfillIndex::Approvals, 1).unwrap(),
+            page("1", true)
+        );
+        assert_eq!(
+            backfill(&mut deps, BackfillIndex::Approvals, 1).unwrap(),
+            page("0", false)
+        );
+        assert_eq!(
+            backfill(&mut deps, BackfillIndex::KycExpiry, 10).unwrap(),
+            page("1", false)
+        );
+        assert_eq!(
+            backfill(&mut deps, BackfillIndex::KycCopies, 10).unwrap(),
+            page("1", false)
+        );
+
+        let approvals =
+            state::list_approvals_by_user(deps.as_ref().storage, &alice, None, usize::MAX).unwrap();
+        let approved_at: Vec<_> = approvals
+            .iter()
+            .map(|(cooperator, approval)| (cooperator.clone(), approval.approved_at))
+            .collect();
+        assert_eq!(
+            approved_at,
+            vec![
+                (bob.clone(), env.block.time),
+                (carol, Timestamp::from_seconds(1))
+            ]
+        );
+        assert_eq!(
+            state::list_expiring(deps.as_ref().storage, env.block.time, None, 10).unwrap(),
+            vec![(1, validity)]
+        );
+
+        // a finished backfill starts over and finds nothing new
+        assert_eq!(
+            backfill(&mut deps, BackfillIndex::KycCopies, 10).unwrap(),
+            page("0", false)
+        );
+        assert_eq!(
+            state::remove_approved_kyc_copies(deps.as_mut().storage, 1).unwrap(),
+            vec![bob]
+        );
+    }
 
     #[test]
     fn test_instantiate() {
@@ -182,9 +775,8 @@ mod tests {
     #[test]
     fn test_integrated() {
         let deployer = Addr::unchecked("deployer");
-        let alice = Addr::unchecked("alice");
-        let bob = Addr::unchecked("bob");
-        let carol = Addr::unchecked("carol");
+        let (alice_key, alice) = keypair(1);
+        let (bob_key, bob) = keypair(2);
 
         // Deploy contract
         let mut app = App::default();
@@ -238,7 +830,7 @@ mod tests {
             &msg::ExecuteMsg::SetKycData {
                 token_id: 1,
                 key: key.clone(),
-                validity: validity.clone(),
+                validity,
                 data: data.clone(),
                 questions: questions.clone(),
             },
@@ -248,7 +840,13 @@ mod tests {
 
         let resp: msg::GetKycDataResponse = app
             .wrap()
-            .query_wasm_smart(addr.clone(), &msg::QueryMsg::GetKycData { token_id: 1 })
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::GetKycData {
+                    token_id: 1,
+                    permit: sign_permit(&app, &addr, &alice_key, &alice, "alice"),
+                },
+            )
             .unwrap();
 
         assert_eq!(
@@ -257,7 +855,7 @@ mod tests {
                 owner: alice.clone(),
                 token_id: 1,
                 key: key.clone(),
-                validity: validity.clone(),
+                validity,
                 data: data.clone(),
                 questions: questions.clone(),
             }
@@ -401,6 +999,7 @@ mod tests {
                 &msg::QueryMsg::GetApprovedUserKycData {
                     cooperator: bob.clone(),
                     user: alice.clone(),
+                    permit: sign_permit(&app, &addr, &bob_key, &bob, "bob"),
                 },
             )
             .unwrap();
@@ -413,7 +1012,7 @@ mod tests {
                 token_id: 1,
                 data: crate::state::KycData {
                     key: cooperator_key.clone(),
-                    validity: validity.clone(),
+                    validity,
                     data: data.clone(),
                     questions: questions.clone()
                 },
@@ -431,5 +1030,3174 @@ mod tests {
             &[],
         )
         .unwrap();
+
+        // a revoked cooperator can no longer read its copy
+        let err = app
+            .wrap()
+            .query_wasm_smart::<msg::GetApprovedUserKycDataResponse>(
+                addr.clone(),
+                &msg::QueryMsg::GetApprovedUserKycData {
+                    cooperator: bob.clone(),
+                    user: alice.clone(),
+                    permit: sign_permit(&app, &addr, &bob_key, &bob, "bob"),
+                },
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("didn't approved"), "{err}");
+    }
+
+    #[test]
+    fn test_list_approvals_by_user() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let carol = Addr::unchecked("carol");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        grant_cooperator(&mut app, &addr, &deployer, &carol);
+        approve(&mut app, &addr, &alice, &bob, token_id);
+        approve(&mut app, &addr, &alice, &carol, token_id);
+
+        let resp: msg::ListApprovalsByUserResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::ListApprovalsByUser {
+                    user: alice.clone(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+
+        let cooperators: Vec<Addr> = resp
+            .approvals
+            .iter()
+            .map(|a| a.cooperator.clone())
+            .collect();
+        assert_eq!(cooperators, vec![bob.clone(), carol.clone()]);
+        assert_eq!(resp.approvals[0].approved_at, app.block_info().time);
+        assert_eq!(
+            resp.approvals[0].expires_at,
+            Some(app.block_info().time.plus_days(30))
+        );
+        assert_eq!(resp.approvals[0].scope, vec!["question".to_string()]);
+
+        // paginate past bob
+        let resp: msg::ListApprovalsByUserResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::ListApprovalsByUser {
+                    user: alice.clone(),
+                    start_after: Some(bob.clone()),
+                    limit: Some(10),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.approvals.len(), 1);
+        assert_eq!(resp.approvals[0].cooperator, carol);
+
+        // revoked approvals disappear from the index
+        app.execute_contract(
+            alice.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::Revoke {
+                cooperator: bob.clone(),
+                token_id,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: msg::ListApprovalsByUserResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &msg::QueryMsg::ListApprovalsByUser {
+                    user: alice,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.approvals.len(), 1);
+        assert_eq!(resp.approvals[0].cooperator, carol);
+    }
+
+    #[test]
+    fn test_approve_many_and_revoke_all() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let carol = Addr::unchecked("carol");
+        let dave = Addr::unchecked("dave");
+
+        let (mut app, addr) = setup(&deployer);
+        attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        grant_cooperator(&mut app, &addr, &deployer, &carol);
+
+        let request = |cooperator: &Addr| msg::ApprovalRequest {
+            cooperator: cooperator.clone(),
+            cooperator_key: "cooperator_key".to_string(),
+        };
+
+        // dave is not a cooperator, only his item fails
+        let resp = app
+            .execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::ApproveMany {
+                    approvals: vec![request(&bob), request(&dave), request(&carol)],
+                },
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            resp.events
+                .iter()
+                .filter(|event| event.ty == "wasm-approve")
+                .count(),
+            2
+        );
+        assert!(resp
+            .events
+            .iter()
+            .any(|event| event.ty == "wasm-approve_failed"
+                && event
+                    .attributes
+                    .iter()
+                    .any(|attr| attr.key == "cooperator" && attr.value == "dave")));
+
+        let resp: msg::ListApprovalsByUserResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::ListApprovalsByUser {
+                    user: alice.clone(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.approvals.len(), 2);
+
+        // batches above the configured limit are rejected
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetBulkLimit { limit: 1 },
+            &[],
+        )
+        .unwrap();
+        let err = app
+            .execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::ApproveMany {
+                    approvals: vec![request(&bob), request(&carol)],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::TooManyItems { limit: 1 }
+        );
+
+        // revoke all works through the approvals in batches of the limit
+        let resp = app
+            .execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::RevokeAll {},
+                &[],
+            )
+            .unwrap();
+        assert!(resp.events.iter().any(|event| event.ty == "wasm"
+            && event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "remaining" && attr.value == "true")));
+
+        app.execute_contract(
+            alice.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::RevokeAll {},
+            &[],
+        )
+        .unwrap();
+
+        for cooperator in [bob, carol] {
+            let resp: msg::HasApprovedResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    addr.clone(),
+                    &msg::QueryMsg::HasApproved {
+                        cooperator,
+                        user: alice.clone(),
+                    },
+                )
+                .unwrap();
+            assert!(!resp.has_approved);
+        }
+    }
+
+    #[test]
+    fn test_delegated_consent() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let carol = Addr::unchecked("carol");
+        let dave = Addr::unchecked("dave");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        grant_cooperator(&mut app, &addr, &deployer, &carol);
+
+        // dave may only manage alice's consent for bob, for one day
+        let expires_at = app.block_info().time.plus_days(1);
+        app.execute_contract(
+            alice.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::GrantDelegate {
+                delegate: dave.clone(),
+                expires_at: Some(expires_at),
+                cooperators: Some(vec![bob.clone()]),
+            },
+            &[],
+        )
+        .unwrap();
+
+        approve(&mut app, &addr, &dave, &bob, token_id);
+        let resp: msg::HasApprovedResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::HasApproved {
+                    cooperator: bob.clone(),
+                    user: alice.clone(),
+                },
+            )
+            .unwrap();
+        assert!(resp.has_approved);
+
+        let err = app
+            .execute_contract(
+                dave.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::Approve {
+                    cooperator: carol.clone(),
+                    token_id,
+                    cooperator_key: "cooperator_key".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidOwner {
+                token_id,
+                owner: alice.clone()
+            }
+        );
+
+        // the grant stops working once expired
+        app.update_block(|block| block.time = expires_at);
+        app.execute_contract(
+            dave.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::Revoke {
+                cooperator: bob.clone(),
+                token_id,
+            },
+            &[],
+        )
+        .unwrap_err();
+
+        let resp: msg::ListDelegatesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::ListDelegates {
+                    owner: alice.clone(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.delegates.len(), 1);
+        assert_eq!(resp.delegates[0].delegate, dave);
+
+        app.execute_contract(
+            alice.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::RevokeDelegate {
+                delegate: dave.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: msg::GetDelegateResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &msg::QueryMsg::GetDelegate {
+                    owner: alice,
+                    delegate: dave,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.grant, None);
+    }
+
+    #[test]
+    fn test_freeze_token() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+        assert!(verify(&app, &addr, &bob, &alice).result);
+
+        // only inspectors and operators may freeze
+        let err = app
+            .execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::FreezeToken {
+                    token_id,
+                    reason: "self".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidInspectorAccount {
+                account: alice.clone()
+            }
+        );
+
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::FreezeToken {
+                token_id,
+                reason: "sanctions_hit".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp = verify(&app, &addr, &bob, &alice);
+        assert!(!resp.result);
+        assert!(resp.frozen);
+
+        let err = app
+            .execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::Approve {
+                    cooperator: bob.clone(),
+                    token_id,
+                    cooperator_key: "cooperator_key".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::FrozenToken { token_id }
+        );
+
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::UnfreezeToken {
+                token_id,
+                reason: "cleared".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        assert!(verify(&app, &addr, &bob, &alice).result);
+
+        let resp: msg::GetFreezeStatusResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &msg::QueryMsg::GetFreezeStatus { token_id })
+            .unwrap();
+        assert!(!resp.frozen);
+
+        let resp: msg::GetFreezeHistoryResponse = app
+            .wrap()
+            .query_wasm_smart(addr, &msg::QueryMsg::GetFreezeHistory { token_id })
+            .unwrap();
+        let actions: Vec<_> = resp
+            .history
+            .iter()
+            .map(|record| (record.action.clone(), record.reason.as_str()))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (FreezeAction::Freeze, "sanctions_hit"),
+                (FreezeAction::Unfreeze, "cleared"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inspect_token() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let auditor = Addr::unchecked("auditor");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        approve(&mut app, &addr, &alice, &bob, token_id);
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::GrantRole {
+                role: INSPECTOR_ROLE.to_string(),
+                user: auditor.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let inspect = msg::ExecuteMsg::InspectToken {
+            token_id,
+            reason: "annual audit".to_string(),
+        };
+        let err = app
+            .execute_contract(alice.clone(), addr.clone(), &inspect, &[])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidInspectorAccount {
+                account: alice.clone()
+            }
+        );
+
+        let resp = app
+            .execute_contract(auditor.clone(), addr.clone(), &inspect, &[])
+            .unwrap();
+        let report: msg::InspectTokenResponse = from_binary(&resp.data.unwrap()).unwrap();
+        assert_eq!(report.owner, alice);
+        assert_eq!(report.approvals.len(), 1);
+        assert_eq!(report.approvals[0].cooperator, bob);
+        assert_eq!(report.kyc_history.len(), 1);
+        assert_eq!(report.kyc_history[0].updated_by, deployer);
+        assert_eq!(report.operator_actions[0].action, "setKycData");
+
+        let resp: msg::ListAuditLogResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::ListAuditLog {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.entries.len(), 1);
+        assert_eq!(resp.entries[0].entry.inspector, auditor);
+        assert_eq!(resp.entries[0].entry.reason, "annual audit");
+
+        // inspectors have no write powers
+        app.execute_contract(
+            auditor,
+            addr,
+            &msg::ExecuteMsg::SetKycData {
+                token_id,
+                key: "key".to_string(),
+                validity: Timestamp::from_seconds(0),
+                data: "data".to_string(),
+                questions: vec![],
+            },
+            &[],
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_query_permits() {
+        let deployer = Addr::unchecked("deployer");
+        let (alice_key, alice) = keypair(1);
+        let (bob_key, bob) = keypair(2);
+        let (carol_key, carol) = keypair(3);
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        approve(&mut app, &addr, &alice, &bob, token_id);
+
+        let user_data = |permit: Permit| msg::QueryMsg::GetUserData {
+            cooperator: bob.clone(),
+            user: alice.clone(),
+            permit,
+        };
+
+        // the approved cooperator and the owner can read
+        let resp: msg::GetUserDataResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &user_data(sign_permit(&app, &addr, &bob_key, &bob, "bob")),
+            )
+            .unwrap();
+        assert_eq!(resp.kyc_data.key, "cooperator_key");
+        app.wrap()
+            .query_wasm_smart::<msg::GetUserDataResponse>(
+                addr.clone(),
+                &user_data(sign_permit(&app, &addr, &alice_key, &alice, "alice")),
+            )
+            .unwrap();
+
+        // others can't, even with a valid permit
+        let err = app
+            .wrap()
+            .query_wasm_smart::<msg::GetUserDataResponse>(
+                addr.clone(),
+                &user_data(sign_permit(&app, &addr, &carol_key, &carol, "carol")),
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("is not allowed to read this data"));
+
+        // a permit claiming someone else's address is rejected
+        let mut forged = sign_permit(&app, &addr, &carol_key, &carol, "carol");
+        forged.signer = bob.clone();
+        let err = app
+            .wrap()
+            .query_wasm_smart::<msg::GetUserDataResponse>(addr.clone(), &user_data(forged))
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid permit"));
+
+        // revoked permits stop working
+        app.execute_contract(
+            bob.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::RevokePermit {
+                permit_name: "bob".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        let err = app
+            .wrap()
+            .query_wasm_smart::<msg::GetUserDataResponse>(
+                addr.clone(),
+                &user_data(sign_permit(&app, &addr, &bob_key, &bob, "bob")),
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("permit bob was revoked"));
+    }
+
+    #[test]
+    fn test_kyc_commitments() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+
+        let salt = Binary::from([7u8; 32].as_slice());
+        let commitment = KycCommitment {
+            data_commitment: Binary::from(crypto::sha256(b"payload").as_slice()),
+            field_commitments: vec![FieldCommitment {
+                field: "country".to_string(),
+                commitment: Binary::from(
+                    crypto::field_commitment(&salt, "country", "Australia").as_slice(),
+                ),
+            }],
+        };
+        let set_commitment = |commitment: KycCommitment| msg::ExecuteMsg::SetKycCommitment {
+            token_id,
+            key: "key".to_string(),
+            validity: Timestamp::from_seconds(0),
+            questions: vec![],
+            commitment,
+        };
+
+        let err = app
+            .execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &set_commitment(KycCommitment {
+                    data_commitment: Binary::from(b"short".as_slice()),
+                    field_commitments: vec![],
+                }),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidCommitment {}
+        );
+
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &set_commitment(commitment.clone()),
+            &[],
+        )
+        .unwrap();
+
+        let resp: msg::GetKycCommitmentResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &msg::QueryMsg::GetKycCommitment { token_id })
+            .unwrap();
+        assert_eq!(resp.commitment, commitment);
+
+        let verify_field = |value: &str| msg::QueryMsg::VerifyField {
+            token_id,
+            field: "country".to_string(),
+            value: value.to_string(),
+            salt: salt.clone(),
+        };
+        let resp: msg::VerifyFieldResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &verify_field("Australia"))
+            .unwrap();
+        assert!(resp.result);
+        let resp: msg::VerifyFieldResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &verify_field("France"))
+            .unwrap();
+        assert!(!resp.result);
+
+        // writing plaintext data again drops the commitments
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetKycData {
+                token_id,
+                key: "key".to_string(),
+                validity: Timestamp::from_seconds(0),
+                data: "data".to_string(),
+                questions: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+        app.wrap()
+            .query_wasm_smart::<msg::GetKycCommitmentResponse>(
+                addr,
+                &msg::QueryMsg::GetKycCommitment { token_id },
+            )
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_attribute_proofs() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+
+        // four leaves: root = node(node(a, b), node(c, d))
+        let leaves = [
+            "age_over_18=true",
+            "age_over_21=false",
+            "country=AU",
+            "sanctioned=false",
+        ]
+        .map(crypto::merkle_leaf);
+        let ab = crypto::merkle_node(&leaves[0], &leaves[1]);
+        let cd = crypto::merkle_node(&leaves[2], &leaves[3]);
+        let root = crypto::merkle_node(&ab, &cd);
+
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetAttributeRoot {
+                token_id,
+                root: Binary::from(root.as_slice()),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let prove = |app: &App, leaf: &str| {
+            app.wrap()
+                .query_wasm_smart::<msg::VerifyAttributeProofResponse>(
+                    addr.clone(),
+                    &msg::QueryMsg::VerifyAttributeProof {
+                        token_id,
+                        leaf: leaf.to_string(),
+                        proof: vec![
+                            Binary::from(leaves[1].as_slice()),
+                            Binary::from(cd.as_slice()),
+                        ],
+                    },
+                )
+        };
+        let included = |app: &App| prove(app, "age_over_18=true").unwrap().included;
+        assert!(included(&app));
+        assert!(!prove(&app, "age_over_18=false").unwrap().included);
+
+        // claims hold only for a screened, unfrozen holder with valid kyc
+        let exec = |app: &mut App, msg: msg::ExecuteMsg| {
+            app.execute_contract(deployer.clone(), addr.clone(), &msg, &[])
+                .unwrap();
+        };
+        exec(
+            &mut app,
+            msg::ExecuteMsg::AddToDenylist {
+                address: alice.clone(),
+                reason: "fraud".to_string(),
+            },
+        );
+        let resp = prove(&app, "age_over_18=true").unwrap();
+        assert!(!resp.included);
+        assert!(resp.denied_reason.is_some());
+        exec(
+            &mut app,
+            msg::ExecuteMsg::RemoveFromDenylist {
+                address: alice.clone(),
+            },
+        );
+        exec(
+            &mut app,
+            msg::ExecuteMsg::FreezeToken {
+                token_id,
+                reason: "review".to_string(),
+            },
+        );
+        assert!(!included(&app));
+        exec(
+            &mut app,
+            msg::ExecuteMsg::UnfreezeToken {
+                token_id,
+                reason: "cleared".to_string(),
+            },
+        );
+        assert!(included(&app));
+
+        app.update_block(|block| block.time = block.time.plus_days(31));
+        assert!(!included(&app));
+        // and the root goes with the kyc record when pruned
+        exec(&mut app, msg::ExecuteMsg::PruneExpired { limit: 10 });
+        assert!(prove(&app, "age_over_18=true")
+            .unwrap_err()
+            .to_string()
+            .contains("kyc"));
+    }
+
+    /// Knowledge of `a * b = product`, bound to a deployment and a token id.
+    struct ProductCircuit {
+        domain: Option<Fr>,
+        token_id: Option<Fr>,
+        product: Option<Fr>,
+        a: Option<Fr>,
+        b: Option<Fr>,
+    }
+
+    impl ConstraintSynthesizer<Fr> for ProductCircuit {
+        fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+            cs.new_input_variable(|| self.domain.ok_or(SynthesisError::AssignmentMissing))?;
+            cs.new_input_variable(|| self.token_id.ok_or(SynthesisError::AssignmentMissing))?;
+            let product =
+                cs.new_input_variable(|| self.product.ok_or(SynthesisError::AssignmentMissing))?;
+            let a = cs.new_witness_variable(|| self.a.ok_or(SynthesisError::AssignmentMissing))?;
+            let b = cs.new_witness_variable(|| self.b.ok_or(SynthesisError::AssignmentMissing))?;
+            cs.enforce_constraint(lc!() + a, lc!() + b, lc!() + product)
+        }
+    }
+
+    #[test]
+    fn test_zk_proof_claims() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let carol = Addr::unchecked("carol");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        let other_token_id = attest_with_kyc(&mut app, &addr, &deployer, &carol);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetQuestions {
+                cooperator: bob.clone(),
+                questions: vec!["zk_question".to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+        assert!(!verify(&app, &addr, &bob, &alice).result);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let setup_circuit = || ProductCircuit {
+            domain: None,
+            token_id: None,
+            product: None,
+            a: None,
+            b: None,
+        };
+        let (pk, vk) = Groth16::<Bn254>::circuit_specific_setup(setup_circuit(), &mut rng).unwrap();
+        let proof = Groth16::<Bn254>::prove(
+            &pk,
+            ProductCircuit {
+                domain: Some(verifier::proof_domain(&app.block_info().chain_id, &addr)),
+                token_id: Some(Fr::from(token_id)),
+                product: Some(Fr::from(21u64)),
+                a: Some(Fr::from(3u64)),
+                b: Some(Fr::from(7u64)),
+            },
+            &mut rng,
+        )
+        .unwrap();
+
+        let serialize_vk = |vk: &VerifyingKey<Bn254>| {
+            let mut vk_bytes = vec![];
+            vk.serialize_compressed(&mut vk_bytes).unwrap();
+            Binary::from(vk_bytes)
+        };
+        let register = |app: &mut App, contract: &Addr, vk: Binary| {
+            app.execute_contract(
+                deployer.clone(),
+                contract.clone(),
+                &msg::ExecuteMsg::RegisterVerifyingKey {
+                    question: "zk_question".to_string(),
+                    vk,
+                },
+                &[],
+            )
+            .unwrap();
+        };
+        let mut proof_bytes = vec![];
+        proof.serialize_compressed(&mut proof_bytes).unwrap();
+        let mut product_bytes = vec![];
+        Fr::from(21u64)
+            .serialize_compressed(&mut product_bytes)
+            .unwrap();
+
+        register(&mut app, &addr, serialize_vk(&vk));
+
+        let submit = |token_id: u64| msg::ExecuteMsg::SubmitProof {
+            token_id,
+            question: "zk_question".to_string(),
+            proof: Binary::from(proof_bytes.clone()),
+            public_inputs: vec![Binary::from(product_bytes.clone())],
+        };
+
+        // the proof is bound to alice's token and can't be replayed for carol
+        let err = app
+            .execute_contract(carol.clone(), addr.clone(), &submit(other_token_id), &[])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidProof {}
+        );
+
+        app.execute_contract(alice.clone(), addr.clone(), &submit(token_id), &[])
+            .unwrap();
+
+        let resp: msg::GetVerifiedClaimResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::GetVerifiedClaim {
+                    token_id,
+                    question: "zk_question".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.claim,
+            Some(VerifiedClaim {
+                submitter: alice.clone(),
+                verified_at: app.block_info().time,
+                key_version: 1,
+            })
+        );
+        assert!(verify(&app, &addr, &bob, &alice).result);
+
+        // nor on another deployment registering the same key
+        let code = ContractWrapper::new(contract::execute, contract::instantiate, contract::query);
+        let code_id = app.store_code(Box::new(code));
+        let other = app
+            .instantiate_contract(code_id, deployer.clone(), &Empty {}, &[], "zkMeSBT", None)
+            .unwrap();
+        assert_eq!(
+            attest_with_kyc(&mut app, &other, &deployer, &alice),
+            token_id
+        );
+        register(&mut app, &other, serialize_vk(&vk));
+        let err = app
+            .execute_contract(alice.clone(), other, &submit(token_id), &[])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidProof {}
+        );
+
+        // replacing the key retires claims proven under the old one
+        let (_, new_vk) =
+            Groth16::<Bn254>::circuit_specific_setup(setup_circuit(), &mut rng).unwrap();
+        register(&mut app, &addr, serialize_vk(&new_vk));
+        assert!(!verify(&app, &addr, &bob, &alice).result);
+        let resp: msg::GetVerifiedClaimResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::GetVerifiedClaim {
+                    token_id,
+                    question: "zk_question".to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.claim, None);
+    }
+
+    #[test]
+    fn test_signed_attestations() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let (attestor, _) = keypair(9);
+
+        let (mut app, addr) = setup(&deployer);
+        app.execute_contract(
+            alice.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::Attest { to: alice.clone() },
+            &[],
+        )
+        .unwrap();
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::AddAttestorKey {
+                key_id: "zkme-1".to_string(),
+                algorithm: KeyAlgorithm::Secp256k1,
+                pubkey: Binary::from(attestor.verifying_key().to_bytes().as_slice()),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let attestation = msg::Attestation {
+            key_id: "zkme-1".to_string(),
+            chain_id: app.block_info().chain_id,
+            contract: addr.clone(),
+            token_id: 1,
+            nonce: 1,
+            kyc: KycData {
+                key: "key".to_string(),
+                validity: app.block_info().time.plus_days(30),
+                data: "data".to_string(),
+                questions: vec!["question".to_string()],
+            },
+        };
+        let submit = |attestation: &msg::Attestation| {
+            let signature: Signature = attestor.sign(&to_vec(attestation).unwrap());
+            msg::ExecuteMsg::SubmitAttestation {
+                attestation: attestation.clone(),
+                signature: Binary::from(signature.as_ref()),
+            }
+        };
+
+        // the holder submits the signed attestation without an operator
+        app.execute_contract(alice.clone(), addr.clone(), &submit(&attestation), &[])
+            .unwrap();
+        assert!(verify(&app, &addr, &bob, &alice).result);
+
+        // replays are rejected
+        let err = app
+            .execute_contract(alice.clone(), addr.clone(), &submit(&attestation), &[])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidAttestationNonce { nonce: 1 }
+        );
+
+        // tampered attestations are rejected
+        let mut tampered = submit(&attestation);
+        if let msg::ExecuteMsg::SubmitAttestation { attestation, .. } = &mut tampered {
+            attestation.nonce = 2;
+            attestation.kyc.questions.push("extra".to_string());
+        }
+        let err = app
+            .execute_contract(alice.clone(), addr.clone(), &tampered, &[])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidAttestation {}
+        );
+
+        // revoked keys are no longer accepted
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::RevokeAttestorKey {
+                key_id: "zkme-1".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        let err = app
+            .execute_contract(
+                alice,
+                addr,
+                &submit(&msg::Attestation {
+                    nonce: 2,
+                    ..attestation
+                }),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidAttestorKey {
+                key_id: "zkme-1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_multi_issuer_attestations() {
+        let deployer = Addr::unchecked("deployer");
+        let (alice_key, alice) = keypair(1);
+        let bob = Addr::unchecked("bob");
+        let partner = Addr::unchecked("partner");
+        let weak = Addr::unchecked("weak");
+
+        let (mut app, addr) = setup(&deployer);
+        app.execute_contract(
+            alice.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::Attest { to: alice.clone() },
+            &[],
+        )
+        .unwrap();
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+
+        for (issuer, trust_level) in [(&partner, 5), (&weak, 1)] {
+            app.execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::RegisterIssuer {
+                    issuer: issuer.clone(),
+                    name: issuer.to_string(),
+                    trust_level,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+        app.execute_contract(
+            weak.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::IssueAttestation {
+                token_id: 1,
+                validity: app.block_info().time.plus_days(30),
+                data: "weak".to_string(),
+                questions: vec!["question".to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let set_policy = |issuer_rule: IssuerRule| msg::ExecuteMsg::SetPolicy {
+            cooperator: bob.clone(),
+            policy: VerifyPolicy {
+                issuer_rule,
+                ..VerifyPolicy::default()
+            },
+        };
+
+        // alice has no zkMe KYC, and the weak issuer isn't trusted enough
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &set_policy(IssuerRule::AnyTrusted { min_trust_level: 3 }),
+            &[],
+        )
+        .unwrap();
+        assert!(!verify(&app, &addr, &bob, &alice).result);
+
+        app.execute_contract(
+            partner.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::IssueAttestation {
+                token_id: 1,
+                validity: app.block_info().time.plus_days(30),
+                data: "partner".to_string(),
+                questions: vec!["question".to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+        assert!(verify(&app, &addr, &bob, &alice).result);
+
+        // a specific issuer rule only accepts that issuer
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &set_policy(IssuerRule::Specific {
+                issuer: weak.clone(),
+            }),
+            &[],
+        )
+        .unwrap();
+        assert!(verify(&app, &addr, &bob, &alice).result);
+        app.execute_contract(
+            weak.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::WithdrawAttestation { token_id: 1 },
+            &[],
+        )
+        .unwrap();
+        assert!(!verify(&app, &addr, &bob, &alice).result);
+        let err = app
+            .execute_contract(
+                weak.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::WithdrawAttestation { token_id: 1 },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NonExistAttestation {
+                token_id: 1,
+                issuer: weak.clone()
+            }
+        );
+
+        // attestation payloads are only readable with the holder's permit
+        let (eve_key, eve) = keypair(2);
+        let err = app
+            .wrap()
+            .query_wasm_smart::<msg::ListAttestationsResponse>(
+                addr.clone(),
+                &msg::QueryMsg::ListAttestations {
+                    token_id: 1,
+                    permit: sign_permit(&app, &addr, &eve_key, &eve, "eve"),
+                },
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("not allowed"));
+        let resp: msg::ListAttestationsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::ListAttestations {
+                    token_id: 1,
+                    permit: sign_permit(&app, &addr, &alice_key, &alice, "alice"),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.attestations.len(), 1);
+        assert_eq!(resp.attestations[0].issuer, partner);
+        assert_eq!(resp.attestations[0].trust_level, Some(5));
+    }
+
+    #[test]
+    fn test_kyc_tiers() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetTiers {
+                tiers: vec!["basic".to_string(), "enhanced".to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::SetTier {
+                    token_id,
+                    tier: "premium".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::NonExistTier {
+                tier: "premium".to_string()
+            }
+        );
+
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetPolicy {
+                cooperator: bob.clone(),
+                policy: VerifyPolicy {
+                    min_tier: Some("enhanced".to_string()),
+                    ..VerifyPolicy::default()
+                },
+            },
+            &[],
+        )
+        .unwrap();
+
+        // no tier yet, then basic, then enhanced
+        assert!(!verify(&app, &addr, &bob, &alice).result);
+        for (tier, expected) in [("basic", false), ("enhanced", true)] {
+            app.execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::SetTier {
+                    token_id,
+                    tier: tier.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(verify(&app, &addr, &bob, &alice).result, expected);
+        }
+
+        let resp: msg::GetTierResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &msg::QueryMsg::GetTier {
+                    user: alice.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp,
+            msg::GetTierResponse {
+                user: alice,
+                tier: Some("enhanced".to_string()),
+                rank: Some(1),
+            }
+        );
+    }
+
+    #[test]
+    fn test_kyc_expiry() {
+        let deployer = Addr::unchecked("deployer");
+        let (alice_key, alice) = keypair(1);
+        let bob = Addr::unchecked("bob");
+        let carol = Addr::unchecked("carol");
+
+        let (mut app, addr) = setup(&deployer);
+        let alice_token = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        let carol_token = attest_with_kyc(&mut app, &addr, &deployer, &carol);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+
+        let now = app.block_info().time;
+        let list_expiring =
+            |app: &App, before: Timestamp, start_after: Option<(Timestamp, u64)>| {
+                let resp: msg::ListExpiringResponse = app
+                    .wrap()
+                    .query_wasm_smart(
+                        addr.clone(),
+                        &msg::QueryMsg::ListExpiring {
+                            before,
+                            start_after,
+                            limit: Some(1),
+                        },
+                    )
+                    .unwrap();
+                resp.tokens
+            };
+
+        assert!(list_expiring(&app, now.plus_days(29), None).is_empty());
+        let first = list_expiring(&app, now.plus_days(31), None);
+        assert_eq!(
+            first,
+            vec![msg::ExpiringToken {
+                token_id: alice_token,
+                owner: alice.clone(),
+                validity: now.plus_days(30),
+            }]
+        );
+        let cursor = Some((first[0].validity, first[0].token_id));
+        let next = list_expiring(&app, now.plus_days(31), cursor);
+        assert_eq!(next[0].token_id, carol_token);
+        approve(&mut app, &addr, &alice, &bob, alice_token);
+
+        // renewal must move validity forward and keeps the data
+        let err = app
+            .execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::RenewKyc {
+                    token_id: alice_token,
+                    validity: now.plus_days(10),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidValidity {
+                validity: now.plus_days(10)
+            }
+        );
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::RenewKyc {
+                token_id: alice_token,
+                validity: now.plus_days(60),
+            },
+            &[],
+        )
+        .unwrap();
+        let kyc: msg::GetKycDataResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::GetKycData {
+                    token_id: alice_token,
+                    permit: sign_permit(&app, &addr, &alice_key, &alice, "alice"),
+                },
+            )
+            .unwrap();
+        assert_eq!(kyc.data, "data");
+        assert_eq!(kyc.validity, now.plus_days(60));
+        // cooperator copies follow the renewal
+        let resp: msg::ListApprovalsByUserResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::ListApprovalsByUser {
+                    user: alice.clone(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.approvals[0].expires_at, Some(now.plus_days(60)));
+        assert_eq!(
+            list_expiring(&app, now.plus_days(31), None)[0].token_id,
+            carol_token
+        );
+        // the cursor of a page still resumes after its last token is renewed
+        assert_eq!(
+            list_expiring(&app, now.plus_days(31), cursor)[0].token_id,
+            carol_token
+        );
+
+        // carol expires, then is accepted again within the grace period
+        app.update_block(|block| block.time = block.time.plus_days(45));
+        assert!(!verify(&app, &addr, &bob, &carol).result);
+
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetGracePeriod {
+                seconds: 30 * 24 * 60 * 60,
+            },
+            &[],
+        )
+        .unwrap();
+        let resp = verify(&app, &addr, &bob, &carol);
+        assert!(resp.result && resp.in_grace);
+        let resp = verify(&app, &addr, &bob, &alice);
+        assert!(resp.result && !resp.in_grace);
+    }
+
+    #[test]
+    fn test_prune_expired() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let carol = Addr::unchecked("carol");
+        let anyone = Addr::unchecked("anyone");
+
+        let (mut app, addr) = setup(&deployer);
+        let alice_token = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        let carol_token = attest_with_kyc(&mut app, &addr, &deployer, &carol);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+        approve(&mut app, &addr, &alice, &bob, alice_token);
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::RenewKyc {
+                token_id: carol_token,
+                validity: app.block_info().time.plus_days(90),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetRetentionPeriod {
+                seconds: 10 * 24 * 60 * 60,
+            },
+            &[],
+        )
+        .unwrap();
+        // an issuer attestation on carol's token expires along with alice's kyc
+        let issuer = Addr::unchecked("issuer");
+        let validity = app.block_info().time.plus_days(30);
+        for (sender, msg) in [
+            (
+                &deployer,
+                msg::ExecuteMsg::SetJurisdiction {
+                    token_id: alice_token,
+                    jurisdiction: "DE".to_string(),
+                },
+            ),
+            (
+                &deployer,
+                msg::ExecuteMsg::RegisterIssuer {
+                    issuer: issuer.clone(),
+                    name: "issuer".to_string(),
+                    trust_level: 1,
+                },
+            ),
+            (
+                &issuer,
+                msg::ExecuteMsg::IssueAttestation {
+                    token_id: carol_token,
+                    validity,
+                    data: "data".to_string(),
+                    questions: vec!["question".to_string()],
+                },
+            ),
+        ] {
+            app.execute_contract(sender.clone(), addr.clone(), &msg, &[])
+                .unwrap();
+        }
+
+        let prune = |app: &mut App| {
+            app.execute_contract(
+                anyone.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::PruneExpired { limit: 10 },
+                &[],
+            )
+            .unwrap()
+        };
+
+        // expired, but still within the retention period
+        app.update_block(|block| block.time = block.time.plus_days(35));
+        let resp = prune(&mut app);
+        assert!(resp.events.iter().all(|event| event.ty != "wasm-prune"));
+
+        // retention only starts counting once the grace period is over
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetGracePeriod {
+                seconds: 5 * 24 * 60 * 60,
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(|block| block.time = block.time.plus_days(7));
+        let resp = prune(&mut app);
+        assert!(resp.events.iter().all(|event| event.ty != "wasm-prune"));
+
+        app.update_block(|block| block.time = block.time.plus_days(4));
+        let resp = prune(&mut app);
+        let pruned: Vec<_> = resp
+            .events
+            .iter()
+            .filter(|event| event.ty == "wasm-prune")
+            .collect();
+        assert_eq!(pruned.len(), 2);
+        assert!(pruned[0]
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "tokenId" && attr.value == alice_token.to_string()));
+        assert!(pruned[0]
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "copies" && attr.value == "1"));
+        assert!(pruned[1]
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "issuer" && attr.value == issuer.as_str()));
+        let resp = prune(&mut app);
+        assert!(resp.events.iter().all(|event| event.ty != "wasm-prune"));
+
+        let err = app
+            .wrap()
+            .query_wasm_smart::<msg::VerifyResponse>(
+                addr.clone(),
+                &msg::QueryMsg::Verify {
+                    cooperator: bob.clone(),
+                    user: alice.clone(),
+                },
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("kyc"));
+        assert!(verify(&app, &addr, &bob, &carol).result);
+
+        // the jurisdiction went with alice's kyc record
+        for msg in [
+            msg::ExecuteMsg::SetKycData {
+                token_id: alice_token,
+                key: "key".to_string(),
+                validity: app.block_info().time.plus_days(30),
+                data: "data".to_string(),
+                questions: vec!["question".to_string()],
+            },
+            msg::ExecuteMsg::SetPolicy {
+                cooperator: bob.clone(),
+                policy: VerifyPolicy {
+                    jurisdictions: Some(JurisdictionRule::Allow {
+                        jurisdictions: vec!["DE".to_string()],
+                    }),
+                    ..VerifyPolicy::default()
+                },
+            },
+        ] {
+            app.execute_contract(deployer.clone(), addr.clone(), &msg, &[])
+                .unwrap();
+        }
+        assert!(!verify(&app, &addr, &bob, &alice).result);
+    }
+
+    #[test]
+    fn test_erase_my_data() {
+        let deployer = Addr::unchecked("deployer");
+        let (alice_key, alice) = keypair(1);
+        let bob = Addr::unchecked("bob");
+        let carol = Addr::unchecked("carol");
+
+        let (mut app, addr) = setup(&deployer);
+        let alice_token = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        attest_with_kyc(&mut app, &addr, &deployer, &carol);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+        approve(&mut app, &addr, &alice, &bob, alice_token);
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetJurisdiction {
+                token_id: alice_token,
+                jurisdiction: "DE".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        for msg in [
+            msg::ExecuteMsg::SetTiers {
+                tiers: vec!["basic".to_string()],
+            },
+            msg::ExecuteMsg::SetTier {
+                token_id: alice_token,
+                tier: "basic".to_string(),
+            },
+        ] {
+            app.execute_contract(deployer.clone(), addr.clone(), &msg, &[])
+                .unwrap();
+        }
+
+        // alice keeps the bare SBT
+        app.execute_contract(
+            alice.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::EraseMyData { keep_token: true },
+            &[],
+        )
+        .unwrap();
+        let erased_at = app.block_info().time;
+
+        let resp = verify(&app, &addr, &bob, &alice);
+        assert!(!resp.result);
+        assert_eq!(resp.erased_at, Some(erased_at));
+
+        let err = app
+            .wrap()
+            .query_wasm_smart::<msg::GetUserDataResponse>(
+                addr.clone(),
+                &msg::QueryMsg::GetUserData {
+                    cooperator: bob.clone(),
+                    user: alice.clone(),
+                    permit: sign_permit(&app, &addr, &alice_key, &alice, "alice"),
+                },
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("was erased"));
+
+        let resp: msg::OwnerOfResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::OwnerOf {
+                    token_id: alice_token,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.owner, alice);
+
+        // nor do the tier or the questions kept in the KYC history survive
+        let resp: msg::GetTierResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::GetTier {
+                    user: alice.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.tier, None);
+        let resp = app
+            .execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::InspectToken {
+                    token_id: alice_token,
+                    reason: "erasure check".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        let report: msg::InspectTokenResponse = from_binary(&resp.data.unwrap()).unwrap();
+        assert!(report.kyc_history.is_empty());
+
+        let approved_token_ids = |app: &App| -> Vec<u64> {
+            let resp: msg::GetApprovedTokenIdResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    addr.clone(),
+                    &msg::QueryMsg::GetApprovedTokenId {
+                        cooperator: bob.clone(),
+                        start: 0,
+                        page_size: 10,
+                    },
+                )
+                .unwrap();
+            resp.token_id_list
+        };
+        let has_approved = |app: &App, user: &Addr| -> bool {
+            let resp: msg::HasApprovedResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    addr.clone(),
+                    &msg::QueryMsg::HasApproved {
+                        cooperator: bob.clone(),
+                        user: user.clone(),
+                    },
+                )
+                .unwrap();
+            resp.has_approved
+        };
+        // erasing revokes every approval along with the copies
+        assert!(!has_approved(&app, &alice));
+        assert_eq!(approved_token_ids(&app), Vec::<u64>::new());
+
+        // carol burns the SBT as well
+        let carol_token = 2;
+        approve(&mut app, &addr, &carol, &bob, carol_token);
+        app.execute_contract(
+            carol.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::EraseMyData { keep_token: false },
+            &[],
+        )
+        .unwrap();
+        let resp: msg::TotalSupplyResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &msg::QueryMsg::TotalSupply {})
+            .unwrap();
+        assert_eq!(resp.total, 1);
+        assert_eq!(verify(&app, &addr, &bob, &carol).erased_at, Some(erased_at));
+        assert!(!has_approved(&app, &carol));
+        assert_eq!(approved_token_ids(&app), Vec::<u64>::new());
+
+        // a fresh attestation clears the tombstone and approvals start over
+        let carol_token = attest_with_kyc(&mut app, &addr, &deployer, &carol);
+        approve(&mut app, &addr, &carol, &bob, carol_token);
+        assert_eq!(approved_token_ids(&app), vec![carol_token]);
+        let resp: msg::GetErasureResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::GetErasure {
+                    user: carol.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.erasure, None);
+        assert!(verify(&app, &addr, &bob, &carol).result);
+
+        // alice's jurisdiction went with the rest of her data
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetKycData {
+                token_id: alice_token,
+                key: "key".to_string(),
+                validity: app.block_info().time.plus_days(30),
+                data: "data".to_string(),
+                questions: vec!["question".to_string()],
+            },
+            &[],
+        )
+        .unwrap();
+        assert!(verify(&app, &addr, &bob, &alice).result);
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetPolicy {
+                cooperator: bob.clone(),
+                policy: VerifyPolicy {
+                    jurisdictions: Some(JurisdictionRule::Allow {
+                        jurisdictions: vec!["DE".to_string()],
+                    }),
+                    ..VerifyPolicy::default()
+                },
+            },
+            &[],
+        )
+        .unwrap();
+        assert!(!verify(&app, &addr, &bob, &alice).result);
+    }
+
+    #[test]
+    fn test_recover_token() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let alice_new = Addr::unchecked("alice_new");
+        let bob = Addr::unchecked("bob");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+        approve(&mut app, &addr, &alice, &bob, token_id);
+
+        let err = app
+            .execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::RecoverToken {
+                    token_id,
+                    new_owner: alice_new.clone(),
+                    move_approvals: true,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidOperatorAccount {
+                account: alice.clone()
+            }
+        );
+
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::RecoverToken {
+                token_id,
+                new_owner: alice_new.clone(),
+                move_approvals: true,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // token, kyc and approvals follow the new wallet
+        assert!(verify(&app, &addr, &bob, &alice_new).result);
+        let resp: msg::HasApprovedResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::HasApproved {
+                    cooperator: bob.clone(),
+                    user: alice_new.clone(),
+                },
+            )
+            .unwrap();
+        assert!(resp.has_approved);
+
+        // the old wallet is retired for good
+        let err = app
+            .execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::Attest { to: alice.clone() },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::RecoveredAccount {
+                account: alice.clone()
+            }
+        );
+        let resp: msg::GetRecoveryResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::GetRecovery {
+                    account: alice.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.recovery.unwrap().new_owner, alice_new);
+    }
+
+    #[test]
+    fn test_guardian_recovery() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let alice_new = Addr::unchecked("alice_new");
+        let bob = Addr::unchecked("bob");
+        let guardians: Vec<_> = ["g1", "g2", "g3"].map(Addr::unchecked).into();
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+
+        let err = app
+            .execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::SetGuardians {
+                    guardians: guardians.clone(),
+                    threshold: 4,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidGuardians {}
+        );
+
+        // guardian sets have their own cap, independent of the bulk limit
+        let set_limit = |app: &mut App, msg: msg::ExecuteMsg| {
+            app.execute_contract(deployer.clone(), addr.clone(), &msg, &[])
+                .unwrap();
+        };
+        let set_guardians = |app: &mut App| {
+            app.execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::SetGuardians {
+                    guardians: guardians.clone(),
+                    threshold: 2,
+                },
+                &[],
+            )
+            .map_err(|err| err.downcast::<ContractError>().unwrap())
+        };
+        set_limit(&mut app, msg::ExecuteMsg::SetGuardianLimit { limit: 2 });
+        assert_eq!(
+            set_guardians(&mut app).unwrap_err(),
+            ContractError::TooManyItems { limit: 2 }
+        );
+        set_limit(&mut app, msg::ExecuteMsg::SetGuardianLimit { limit: 3 });
+        set_limit(&mut app, msg::ExecuteMsg::SetBulkLimit { limit: 1 });
+        set_guardians(&mut app).unwrap();
+        let resp: msg::GuardianLimitResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &msg::QueryMsg::GuardianLimit {})
+            .unwrap();
+        assert_eq!(resp.limit, 3);
+
+        let run = |app: &mut App, sender: &Addr, msg: msg::ExecuteMsg| {
+            app.execute_contract(sender.clone(), addr.clone(), &msg, &[])
+                .map_err(|err| err.downcast::<ContractError>().unwrap())
+        };
+        let propose = msg::ExecuteMsg::ProposeRecovery {
+            token_id,
+            new_owner: alice_new.clone(),
+        };
+
+        // the owner can veto a proposal
+        run(&mut app, &guardians[0], propose.clone()).unwrap();
+        run(
+            &mut app,
+            &alice,
+            msg::ExecuteMsg::CancelRecovery { token_id },
+        )
+        .unwrap();
+
+        run(&mut app, &guardians[0], propose).unwrap();
+        assert_eq!(
+            run(
+                &mut app,
+                &guardians[0],
+                msg::ExecuteMsg::ApproveRecovery { token_id }
+            )
+            .unwrap_err(),
+            ContractError::AlreadyApprovedRecovery {
+                guardian: guardians[0].clone()
+            }
+        );
+        run(
+            &mut app,
+            &guardians[1],
+            msg::ExecuteMsg::ApproveRecovery { token_id },
+        )
+        .unwrap();
+        assert_eq!(
+            run(
+                &mut app,
+                &bob,
+                msg::ExecuteMsg::ExecuteRecovery { token_id }
+            )
+            .unwrap_err(),
+            ContractError::RecoveryNotReady { token_id }
+        );
+
+        app.update_block(|block| block.time = block.time.plus_days(3));
+        run(
+            &mut app,
+            &bob,
+            msg::ExecuteMsg::ExecuteRecovery { token_id },
+        )
+        .unwrap();
+        assert!(verify(&app, &addr, &bob, &alice_new).result);
+
+        let resp: msg::GetGuardiansResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &msg::QueryMsg::GetGuardians { token_id })
+            .unwrap();
+        assert_eq!(resp.config.unwrap().threshold, 2);
+        assert_eq!(resp.pending, None);
+
+        // an operator recovery drops a guardian proposal in flight
+        let alice_third = Addr::unchecked("alice_third");
+        let propose = msg::ExecuteMsg::ProposeRecovery {
+            token_id,
+            new_owner: Addr::unchecked("alice_other"),
+        };
+        run(&mut app, &guardians[0], propose).unwrap();
+        run(
+            &mut app,
+            &guardians[1],
+            msg::ExecuteMsg::ApproveRecovery { token_id },
+        )
+        .unwrap();
+        run(
+            &mut app,
+            &deployer,
+            msg::ExecuteMsg::RecoverToken {
+                token_id,
+                new_owner: alice_third.clone(),
+                move_approvals: true,
+            },
+        )
+        .unwrap();
+        app.update_block(|block| block.time = block.time.plus_days(3));
+        assert_eq!(
+            run(
+                &mut app,
+                &bob,
+                msg::ExecuteMsg::ExecuteRecovery { token_id }
+            )
+            .unwrap_err(),
+            ContractError::NonExistRecovery { token_id }
+        );
+        assert!(verify(&app, &addr, &bob, &alice_third).result);
+    }
+
+    #[test]
+    fn test_adr036_sign_bytes() {
+        // the doc Keplr's signArbitrary signs for the string "hello"
+        let signer = Addr::unchecked("sei1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu");
+        assert_eq!(
+            String::from_utf8(crypto::adr036_sign_bytes(&signer, b"hello")).unwrap(),
+            r#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","msgs":[{"type":"sign/MsgSignData","value":{"data":"aGVsbG8=","signer":"sei1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu"}}],"sequence":"0"}"#
+        );
+    }
+
+    #[test]
+    fn test_linked_addresses() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let (hot_key, hot) = keypair(3);
+        let (cold_key, cold) = keypair(4);
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+        approve(&mut app, &addr, &alice, &bob, token_id);
+
+        let link = |app: &mut App, key: &SigningKey, address: &Addr| {
+            let params = msg::LinkParams {
+                chain_id: app.block_info().chain_id,
+                contract: addr.clone(),
+                token_id,
+                address: address.clone(),
+            };
+            let signature: Signature = key.sign(&crypto::adr036_sign_bytes(
+                address,
+                &to_vec(&params).unwrap(),
+            ));
+            app.execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::LinkAddress {
+                    address: address.clone(),
+                    pubkey: Binary::from(key.verifying_key().to_bytes().as_slice()),
+                    signature: Binary::from(signature.as_ref()),
+                },
+                &[],
+            )
+            .map_err(|err| err.downcast::<ContractError>().unwrap())
+        };
+
+        // the linked wallet itself has to sign
+        assert_eq!(
+            link(&mut app, &cold_key, &hot).unwrap_err(),
+            ContractError::InvalidLinkProof {}
+        );
+        link(&mut app, &hot_key, &hot).unwrap();
+
+        assert!(verify(&app, &addr, &bob, &hot).result);
+        let resp: msg::HasApprovedResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::HasApproved {
+                    cooperator: bob.clone(),
+                    user: hot.clone(),
+                },
+            )
+            .unwrap();
+        assert!(resp.has_approved);
+
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetLinkLimit { limit: 1 },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            link(&mut app, &cold_key, &cold).unwrap_err(),
+            ContractError::TooManyItems { limit: 1 }
+        );
+
+        let err = app
+            .execute_contract(
+                hot.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::Attest { to: hot.clone() },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::AlreadyLinkedAddress {
+                address: hot.clone()
+            }
+        );
+
+        app.execute_contract(
+            hot.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::UnlinkAddress {
+                address: hot.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+        let resp: msg::ListLinkedAddressesResponse = app
+            .wrap()
+            .query_wasm_smart(addr, &msg::QueryMsg::ListLinkedAddresses { token_id })
+            .unwrap();
+        assert!(resp.addresses.is_empty());
+    }
+
+    #[test]
+    fn test_evm_address_mapping() {
+        let deployer = Addr::unchecked("deployer");
+        let bob = Addr::unchecked("bob");
+
+        // private key 1, whose EVM address is a well known test vector
+        let mut secret = [0u8; 32];
+        secret[31] = 1;
+        let key = SigningKey::from_bytes(&secret).unwrap();
+        let compressed = key.verifying_key().to_bytes();
+        let pubkey = Binary::from(key.verifying_key().to_encoded_point(false).as_bytes());
+        let evm_address = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf";
+
+        let (mut app, addr) = setup(&deployer);
+        let resp: msg::DeriveAddressesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::DeriveAddresses {
+                    pubkey: pubkey.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.evm_address, evm_address.to_lowercase());
+        let alice = Addr::unchecked(resp.address);
+        assert!(crypto::is_pubkey_account(&alice, &compressed));
+
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+        approve(&mut app, &addr, &alice, &bob, token_id);
+
+        let verify_evm = |app: &App| {
+            app.wrap().query_wasm_smart::<msg::VerifyResponse>(
+                addr.clone(),
+                &msg::QueryMsg::VerifyEvm {
+                    cooperator: bob.clone(),
+                    evm_address: evm_address.to_string(),
+                },
+            )
+        };
+        assert!(verify_evm(&app)
+            .unwrap_err()
+            .to_string()
+            .contains("is not mapped"));
+
+        // the key has to sign for the sender it derives
+        let params = msg::EvmLinkParams {
+            chain_id: app.block_info().chain_id,
+            contract: addr.clone(),
+            address: alice.clone(),
+        };
+        let signature: Signature = key.sign(&crypto::adr036_sign_bytes(
+            &params.address,
+            &to_vec(&params).unwrap(),
+        ));
+        let err = app
+            .execute_contract(
+                bob.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::RegisterEvmAddress {
+                    pubkey: pubkey.clone(),
+                    signature: Binary::from(signature.as_ref()),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidEvmPubkey {}
+        );
+        app.execute_contract(
+            alice.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::RegisterEvmAddress {
+                pubkey,
+                signature: Binary::from(signature.as_ref()),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp = verify_evm(&app).unwrap();
+        assert!(resp.result);
+        assert_eq!(resp.user, alice);
+        let resp: msg::HasApprovedResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &msg::QueryMsg::HasApprovedEvm {
+                    cooperator: bob,
+                    evm_address: evm_address.to_string(),
+                },
+            )
+            .unwrap();
+        assert!(resp.has_approved);
+    }
+
+    #[test]
+    fn test_denylist_screening() {
+        use cosmwasm_std::{to_binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+
+        fn screening_query(
+            _deps: Deps,
+            _env: Env,
+            msg: msg::ScreeningQueryMsg,
+        ) -> StdResult<Binary> {
+            let msg::ScreeningQueryMsg::Screen { address } = msg;
+            let denied = address.as_str() == "mallory";
+            to_binary(&msg::ScreeningResponse {
+                denied,
+                reason: denied.then(|| "sanctioned".to_string()),
+            })
+        }
+        fn screening_noop(
+            _deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            _msg: Empty,
+        ) -> StdResult<Response> {
+            Ok(Response::default())
+        }
+
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let mallory = Addr::unchecked("mallory");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+
+        // operators deny an address locally
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::AddToDenylist {
+                address: alice.clone(),
+                reason: "fraud".to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        let resp = verify(&app, &addr, &bob, &alice);
+        assert!(!resp.result);
+        assert_eq!(resp.denied_reason, Some("fraud".to_string()));
+        let err = app
+            .execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::Approve {
+                    cooperator: bob.clone(),
+                    token_id,
+                    cooperator_key: "cooperator_key".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::DeniedAddress {
+                address: alice.clone(),
+                reason: "fraud".to_string()
+            }
+        );
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::RemoveFromDenylist {
+                address: alice.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+        approve(&mut app, &addr, &alice, &bob, token_id);
+        assert!(verify(&app, &addr, &bob, &alice).result);
+
+        // and an external screening contract blocks attestation
+        let code = ContractWrapper::new(screening_noop, screening_noop, screening_query);
+        let code_id = app.store_code(Box::new(code));
+        let screening = app
+            .instantiate_contract(code_id, deployer.clone(), &Empty {}, &[], "screening", None)
+            .unwrap();
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetScreeningContract {
+                contract: Some(screening),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                mallory.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::Attest {
+                    to: mallory.clone(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::DeniedAddress {
+                address: mallory.clone(),
+                reason: "sanctioned".to_string()
+            }
+        );
+        assert!(verify(&app, &addr, &bob, &alice).result);
+
+        // denied addresses can't receive the identity through a recovery
+        let err = app
+            .execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::RecoverToken {
+                    token_id,
+                    new_owner: mallory.clone(),
+                    move_approvals: true,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::DeniedAddress {
+                address: mallory,
+                reason: "sanctioned".to_string()
+            }
+        );
+
+        // nor through a link, and a linked wallet denied later fails verify
+        let (hot_key, hot) = keypair(5);
+        let deny_hot = |app: &mut App| {
+            app.execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::AddToDenylist {
+                    address: hot.clone(),
+                    reason: "mule".to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        };
+        deny_hot(&mut app);
+        assert_eq!(
+            link_address(&mut app, &addr, &alice, &hot_key, &hot, token_id).unwrap_err(),
+            ContractError::DeniedAddress {
+                address: hot.clone(),
+                reason: "mule".to_string()
+            }
+        );
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::RemoveFromDenylist {
+                address: hot.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+        link_address(&mut app, &addr, &alice, &hot_key, &hot, token_id).unwrap();
+        assert!(verify(&app, &addr, &bob, &hot).result);
+        deny_hot(&mut app);
+        let resp = verify(&app, &addr, &bob, &hot);
+        assert!(!resp.result);
+        assert_eq!(resp.denied_reason, Some("mule".to_string()));
+        assert!(verify(&app, &addr, &bob, &alice).result);
+    }
+
+    #[test]
+    fn test_jurisdiction_rules() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let carol = Addr::unchecked("carol");
+        let dave = Addr::unchecked("dave");
+
+        let (mut app, addr) = setup(&deployer);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+        for (user, jurisdiction) in [(&alice, Some("DE")), (&carol, Some("US-CA")), (&dave, None)] {
+            let token_id = attest_with_kyc(&mut app, &addr, &deployer, user);
+            if let Some(jurisdiction) = jurisdiction {
+                app.execute_contract(
+                    deployer.clone(),
+                    addr.clone(),
+                    &msg::ExecuteMsg::SetJurisdiction {
+                        token_id,
+                        jurisdiction: jurisdiction.to_string(),
+                    },
+                    &[],
+                )
+                .unwrap();
+            }
+        }
+
+        let err = app
+            .execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::SetJurisdiction {
+                    token_id: 1,
+                    jurisdiction: "Germany".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::InvalidJurisdiction {
+                code: "Germany".to_string()
+            }
+        );
+
+        let rules = [
+            (
+                JurisdictionRule::Deny {
+                    jurisdictions: vec!["US".to_string()],
+                },
+                [true, false, false],
+            ),
+            (
+                JurisdictionRule::Allow {
+                    jurisdictions: vec!["DE".to_string(), "US-CA".to_string()],
+                },
+                [true, true, false],
+            ),
+        ];
+        for (rule, expected) in rules {
+            app.execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::SetPolicy {
+                    cooperator: bob.clone(),
+                    policy: VerifyPolicy {
+                        jurisdictions: Some(rule),
+                        ..VerifyPolicy::default()
+                    },
+                },
+                &[],
+            )
+            .unwrap();
+            for (user, expected) in [&alice, &carol, &dave].into_iter().zip(expected) {
+                assert_eq!(verify(&app, &addr, &bob, user).result, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_age_claims() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::SetPolicy {
+                cooperator: bob.clone(),
+                policy: VerifyPolicy {
+                    min_age: Some(21),
+                    ..VerifyPolicy::default()
+                },
+            },
+            &[],
+        )
+        .unwrap();
+        assert!(!verify(&app, &addr, &bob, &alice).result);
+
+        let set_age_claims = |app: &mut App, over: Vec<u8>| {
+            app.execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::SetAgeClaims { token_id, over },
+                &[],
+            )
+            .unwrap();
+        };
+        let is_over_age = |app: &App, age: u8| {
+            let resp: msg::IsOverAgeResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    addr.clone(),
+                    &msg::QueryMsg::IsOverAge {
+                        user: alice.clone(),
+                        age,
+                    },
+                )
+                .unwrap();
+            resp.result
+        };
+
+        set_age_claims(&mut app, vec![18]);
+        assert!(is_over_age(&app, 16));
+        assert!(is_over_age(&app, 18));
+        assert!(!is_over_age(&app, 21));
+        assert!(!verify(&app, &addr, &bob, &alice).result);
+
+        set_age_claims(&mut app, vec![21, 18]);
+        assert!(is_over_age(&app, 21));
+        assert!(verify(&app, &addr, &bob, &alice).result);
+
+        // screening applies to age checks too
+        let exec = |app: &mut App, msg: msg::ExecuteMsg| {
+            app.execute_contract(deployer.clone(), addr.clone(), &msg, &[])
+                .unwrap();
+        };
+        exec(
+            &mut app,
+            msg::ExecuteMsg::AddToDenylist {
+                address: alice.clone(),
+                reason: "fraud".to_string(),
+            },
+        );
+        assert!(!is_over_age(&app, 21));
+        exec(
+            &mut app,
+            msg::ExecuteMsg::RemoveFromDenylist {
+                address: alice.clone(),
+            },
+        );
+
+        // and claims lapse with the kyc record, then go with it when pruned
+        app.update_block(|block| block.time = block.time.plus_days(31));
+        assert!(!is_over_age(&app, 21));
+        exec(&mut app, msg::ExecuteMsg::PruneExpired { limit: 10 });
+        let validity = app.block_info().time.plus_days(30);
+        exec(
+            &mut app,
+            msg::ExecuteMsg::SetKycData {
+                token_id,
+                key: "key".to_string(),
+                validity,
+                data: "data".to_string(),
+                questions: vec!["question".to_string()],
+            },
+        );
+        assert!(!is_over_age(&app, 18));
+    }
+
+    #[test]
+    fn test_pause_flags() {
+        let deployer = Addr::unchecked("deployer");
+        let pauser = Addr::unchecked("pauser");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let carol = Addr::unchecked("carol");
+
+        let (mut app, addr) = setup(&deployer);
+        let token_id = attest_with_kyc(&mut app, &addr, &deployer, &alice);
+        grant_cooperator(&mut app, &addr, &deployer, &bob);
+        set_questions(&mut app, &addr, &deployer, &bob);
+        app.execute_contract(
+            deployer.clone(),
+            addr.clone(),
+            &msg::ExecuteMsg::GrantRole {
+                role: PAUSER_ROLE.to_string(),
+                user: pauser.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let set_flags = |app: &mut App, sender: &Addr, flags: PauseFlags| {
+            app.execute_contract(
+                sender.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::SetPauseFlags { flags },
+                &[],
+            )
+            .map_err(|err| err.downcast::<ContractError>().unwrap())
+        };
+        assert_eq!(
+            set_flags(&mut app, &alice, PauseFlags::default()).unwrap_err(),
+            ContractError::InvalidAdminAccount {
+                account: alice.clone()
+            }
+        );
+        set_flags(
+            &mut app,
+            &pauser,
+            PauseFlags {
+                attest: true,
+                approvals: true,
+                ..PauseFlags::default()
+            },
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                carol.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::Attest { to: carol.clone() },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Paused {
+                operation: "attest".to_string()
+            }
+        );
+        let err = app
+            .execute_contract(
+                alice.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::Approve {
+                    cooperator: bob.clone(),
+                    token_id,
+                    cooperator_key: "cooperator_key".to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::Paused {
+                operation: "approvals".to_string()
+            }
+        );
+        assert!(verify(&app, &addr, &bob, &alice).result);
+
+        set_flags(
+            &mut app,
+            &pauser,
+            PauseFlags {
+                verification: true,
+                ..PauseFlags::default()
+            },
+        )
+        .unwrap();
+        let err = app
+            .wrap()
+            .query_wasm_smart::<msg::VerifyResponse>(
+                addr.clone(),
+                &msg::QueryMsg::Verify {
+                    cooperator: bob.clone(),
+                    user: alice.clone(),
+                },
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("verification is paused"));
+        approve(&mut app, &addr, &alice, &bob, token_id);
+
+        // attribute writes and recoveries stop with the other kyc writes
+        set_flags(
+            &mut app,
+            &pauser,
+            PauseFlags {
+                kyc_writes: true,
+                ..PauseFlags::default()
+            },
+        )
+        .unwrap();
+        let msgs = [
+            msg::ExecuteMsg::SetJurisdiction {
+                token_id,
+                jurisdiction: "DE".to_string(),
+            },
+            msg::ExecuteMsg::SetAgeClaims {
+                token_id,
+                over: vec![18],
+            },
+            msg::ExecuteMsg::RecoverToken {
+                token_id,
+                new_owner: carol.clone(),
+                move_approvals: true,
+            },
+        ];
+        for msg in msgs {
+            let err = app
+                .execute_contract(deployer.clone(), addr.clone(), &msg, &[])
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::Paused {
+                    operation: "kyc writes".to_string()
+                }
+            );
+        }
+
+        set_flags(&mut app, &deployer, PauseFlags::default()).unwrap();
+        assert!(verify(&app, &addr, &bob, &alice).result);
+    }
+
+    #[test]
+    fn test_two_step_ownership() {
+        let deployer = Addr::unchecked("deployer");
+        let typo = Addr::unchecked("typo");
+        let new_admin = Addr::unchecked("new_admin");
+
+        let (mut app, addr) = setup(&deployer);
+        let ownership = |app: &App| -> Ownership {
+            app.wrap()
+                .query_wasm_smart(addr.clone(), &msg::QueryMsg::Ownership {})
+                .unwrap()
+        };
+        let update = |app: &mut App, sender: &Addr, action: msg::OwnershipAction| {
+            app.execute_contract(
+                sender.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::UpdateOwnership(action),
+                &[],
+            )
+            .map_err(|err| err.downcast::<ContractError>().unwrap())
+        };
+        assert_eq!(ownership(&app).owner, Some(deployer.clone()));
+
+        let err = app
+            .execute_contract(
+                deployer.clone(),
+                addr.clone(),
+                &msg::ExecuteMsg::RevokeRole {
+                    role: DEFAULT_ADMIN_ROLE.to_string(),
+                    user: deployer.clone(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<ContractError>().unwrap(),
+            ContractError::CannotRevokeOwner {
+                account: deployer.clone()
+            }
+        );
+
+        // a typo'd proposal simply lapses
+        let height = app.block_info().height;
+        update(
+            &mut app,
+            &deployer,
+            msg::OwnershipAction::TransferOwnership {
+                new_owner: typo.clone(),
+                expiry: Some(Expiration::AtHeight(height + 5)),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            update(
+                &mut app,
+                &new_admin,
+                msg::OwnershipAction::AcceptOwnership {}
+            )
+            .unwrap_err(),
+            ContractError::InvalidPendingOwner {
+                account: new_admin.clone()
+            }
+        );
+        app.update_block(|block| block.height += 10);
+        assert_eq!(
+            update(&mut app, &typo, msg::OwnershipAction::AcceptOwnership {}).unwrap_err(),
+            ContractError::OwnershipTransferExpired {}
+        );
+        assert_eq!(ownership(&app).owner, Some(deployer.clone()));
+
+        update(
+            &mut app,
+            &deployer,
+            msg::OwnershipAction::TransferOwnership {
+                new_owner: new_admin.clone(),
+                expiry: None,
+            },
+        )
+        .unwrap();
+        update(
+            &mut app,
+            &new_admin,
+            msg::OwnershipAction::AcceptOwnership {},
+        )
+        .unwrap();
+        assert_eq!(
+            ownership(&app),
+            Ownership {
+                owner: Some(new_admin.clone()),
+                pending_owner: None,
+                pending_expiry: None,
+            }
+        );
+
+        // the admin role moved along with ownership
+        for (user, expected) in [(&deployer, false), (&new_admin, true)] {
+            let resp: msg::IsRoleResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    addr.clone(),
+                    &msg::QueryMsg::IsRole {
+                        role: DEFAULT_ADMIN_ROLE.to_string(),
+                        user: user.clone(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(resp.result, expected);
+        }
+        assert_eq!(
+            update(
+                &mut app,
+                &deployer,
+                msg::OwnershipAction::RenounceOwnership {}
+            )
+            .unwrap_err(),
+            ContractError::InvalidOwnerAccount { account: deployer }
+        );
+    }
+
+    #[test]
+    fn test_timelock() {
+        let deployer = Addr::unchecked("deployer");
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let stranger = Addr::unchecked("stranger");
+
+        let (mut app, addr) = setup(&deployer);
+        let exec = |app: &mut App, sender: &Addr, msg: msg::ExecuteMsg| {
+            app.execute_contract(sender.clone(), addr.clone(), &msg, &[])
+                .map_err(|err| err.downcast::<ContractError>().unwrap())
+        };
+        let is_role = |app: &App, role: &str, user: &Addr| -> bool {
+            let resp: msg::IsRoleResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    addr.clone(),
+                    &msg::QueryMsg::IsRole {
+                        role: role.to_string(),
+                        user: user.clone(),
+                    },
+                )
+                .unwrap();
+            resp.result
+        };
+
+        // without a delay the first change applies immediately
+        let delay = 24 * 60 * 60;
+        exec(
+            &mut app,
+            &deployer,
+            msg::ExecuteMsg::SetTimelockDelay { seconds: delay },
+        )
+        .unwrap();
+
+        exec(
+            &mut app,
+            &deployer,
+            msg::ExecuteMsg::SetTokenBaseUri {
+                uri: "ipfs://new/".to_string(),
+            },
+        )
+        .unwrap();
+        exec(
+            &mut app,
+            &deployer,
+            msg::ExecuteMsg::GrantRole {
+                role: OPERATOR_ROLE.to_string(),
+                user: alice.clone(),
+            },
+        )
+        .unwrap();
+        // roles outside the trust model are not timelocked
+        exec(
+            &mut app,
+            &deployer,
+            msg::ExecuteMsg::GrantRole {
+                role: COOPERATOR_ROLE.to_string(),
+                user: bob.clone(),
+            },
+        )
+        .unwrap();
+        assert!(is_role(&app, COOPERATOR_ROLE, &bob));
+        assert!(!is_role(&app, OPERATOR_ROLE, &alice));
+
+        let queued_at = app.block_info().time;
+        let resp: msg::PendingActionsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::PendingActions {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.delay, delay);
+        assert_eq!(
+            resp.actions,
+            vec![
+                msg::PendingAction {
+                    id: 1,
+                    queued: QueuedAction {
+                        action: TimelockAction::SetTokenBaseUri {
+                            uri: "ipfs://new/".to_string()
+                        },
+                        proposed_by: deployer.clone(),
+                        queued_at,
+                        eta: queued_at.plus_seconds(delay),
+                    },
+                },
+                msg::PendingAction {
+                    id: 2,
+                    queued: QueuedAction {
+                        action: TimelockAction::GrantRole {
+                            role: OPERATOR_ROLE.to_string(),
+                            user: alice.clone(),
+                        },
+                        proposed_by: deployer.clone(),
+                        queued_at,
+                        eta: queued_at.plus_seconds(delay),
+                    },
+                },
+            ]
+        );
+
+        assert_eq!(
+            exec(
+                &mut app,
+                &stranger,
+                msg::ExecuteMsg::ExecuteAction { id: 2 }
+            )
+            .unwrap_err(),
+            ContractError::TimelockNotReady {
+                id: 2,
+                eta: queued_at.plus_seconds(delay),
+            }
+        );
+        assert_eq!(
+            exec(&mut app, &stranger, msg::ExecuteMsg::CancelAction { id: 1 }).unwrap_err(),
+            ContractError::InvalidAdminAccount {
+                account: stranger.clone()
+            }
+        );
+        exec(&mut app, &deployer, msg::ExecuteMsg::CancelAction { id: 1 }).unwrap();
+
+        app.update_block(|block| block.time = block.time.plus_seconds(delay));
+        exec(
+            &mut app,
+            &stranger,
+            msg::ExecuteMsg::ExecuteAction { id: 2 },
+        )
+        .unwrap();
+        assert!(is_role(&app, OPERATOR_ROLE, &alice));
+        for id in [1, 2] {
+            assert_eq!(
+                exec(&mut app, &stranger, msg::ExecuteMsg::ExecuteAction { id }).unwrap_err(),
+                ContractError::NonExistQueuedAction { id }
+            );
+        }
+
+        // lowering the delay goes through the timelock as well
+        exec(
+            &mut app,
+            &deployer,
+            msg::ExecuteMsg::SetTimelockDelay { seconds: 0 },
+        )
+        .unwrap();
+        let resp: msg::PendingActionsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::PendingActions {
+                    start_after: Some(2),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.delay, delay);
+        assert_eq!(
+            resp.actions[0].queued.action,
+            TimelockAction::SetTimelockDelay { seconds: 0 }
+        );
+
+        // ownership proposals and question changes wait as well
+        exec(
+            &mut app,
+            &deployer,
+            msg::ExecuteMsg::UpdateOwnership(msg::OwnershipAction::TransferOwnership {
+                new_owner: alice.clone(),
+                expiry: None,
+            }),
+        )
+        .unwrap();
+        exec(
+            &mut app,
+            &deployer,
+            msg::ExecuteMsg::SetQuestions {
+                cooperator: bob.clone(),
+                questions: vec!["question".to_string()],
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            exec(
+                &mut app,
+                &alice,
+                msg::ExecuteMsg::UpdateOwnership(msg::OwnershipAction::AcceptOwnership {}),
+            )
+            .unwrap_err(),
+            ContractError::NonExistOwnershipTransfer {}
+        );
+        let resp: msg::GetQuestionsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::GetQuestions {
+                    cooperator: bob.clone(),
+                },
+            )
+            .unwrap();
+        assert!(resp.questions.is_empty());
+
+        app.update_block(|block| block.time = block.time.plus_seconds(delay));
+        for id in [4, 5] {
+            exec(&mut app, &stranger, msg::ExecuteMsg::ExecuteAction { id }).unwrap();
+        }
+        exec(
+            &mut app,
+            &alice,
+            msg::ExecuteMsg::UpdateOwnership(msg::OwnershipAction::AcceptOwnership {}),
+        )
+        .unwrap();
+        assert!(is_role(&app, DEFAULT_ADMIN_ROLE, &alice));
+        let resp: msg::GetQuestionsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::GetQuestions {
+                    cooperator: bob.clone(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.questions, vec!["question".to_string()]);
+
+        // revoking the proposer's role drops what they queued
+        exec(
+            &mut app,
+            &alice,
+            msg::ExecuteMsg::GrantRole {
+                role: OPERATOR_ROLE.to_string(),
+                user: stranger.clone(),
+            },
+        )
+        .unwrap();
+        exec(
+            &mut app,
+            &alice,
+            msg::ExecuteMsg::RevokeRole {
+                role: OPERATOR_ROLE.to_string(),
+                user: alice.clone(),
+            },
+        )
+        .unwrap();
+        app.update_block(|block| block.time = block.time.plus_seconds(delay));
+        assert_eq!(
+            exec(
+                &mut app,
+                &stranger,
+                msg::ExecuteMsg::ExecuteAction { id: 6 }
+            )
+            .unwrap_err(),
+            ContractError::InvalidOperatorAccount {
+                account: alice.clone()
+            }
+        );
+        assert!(!is_role(&app, OPERATOR_ROLE, &stranger));
+
+        // attestor keys, issuers, tiers, the grace period and inspector
+        // grants change what partners rely on and wait too
+        let trust_changes = [
+            TimelockAction::AddAttestorKey {
+                key_id: "attestor".to_string(),
+                algorithm: KeyAlgorithm::Ed25519,
+                pubkey: Binary::from([7u8; 32].as_slice()),
+            },
+            TimelockAction::RegisterIssuer {
+                issuer: bob.clone(),
+                name: "issuer".to_string(),
+                trust_level: 1,
+            },
+            TimelockAction::RemoveIssuer {
+                issuer: bob.clone(),
+            },
+            TimelockAction::SetTiers {
+                tiers: vec!["basic".to_string()],
+            },
+            TimelockAction::SetGracePeriod { seconds: 60 },
+        ];
+        for msg in [
+            msg::ExecuteMsg::AddAttestorKey {
+                key_id: "attestor".to_string(),
+                algorithm: KeyAlgorithm::Ed25519,
+                pubkey: Binary::from([7u8; 32].as_slice()),
+            },
+            msg::ExecuteMsg::RegisterIssuer {
+                issuer: bob.clone(),
+                name: "issuer".to_string(),
+                trust_level: 1,
+            },
+            msg::ExecuteMsg::RemoveIssuer {
+                issuer: bob.clone(),
+            },
+            msg::ExecuteMsg::SetTiers {
+                tiers: vec!["basic".to_string()],
+            },
+            msg::ExecuteMsg::SetGracePeriod { seconds: 60 },
+        ] {
+            exec(&mut app, &alice, msg).unwrap();
+        }
+        exec(
+            &mut app,
+            &deployer,
+            msg::ExecuteMsg::GrantRole {
+                role: INSPECTOR_ROLE.to_string(),
+                user: stranger.clone(),
+            },
+        )
+        .unwrap();
+        let resp: msg::PendingActionsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                addr.clone(),
+                &msg::QueryMsg::PendingActions {
+                    start_after: Some(6),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        let queued: Vec<_> = resp
+            .actions
+            .into_iter()
+            .map(|pending| pending.queued.action)
+            .collect();
+        assert_eq!(queued[..5], trust_changes[..]);
+        assert_eq!(
+            queued[5],
+            TimelockAction::GrantRole {
+                role: INSPECTOR_ROLE.to_string(),
+                user: stranger.clone(),
+            }
+        );
+        assert!(!is_role(&app, INSPECTOR_ROLE, &stranger));
+        let resp: msg::GracePeriodResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &msg::QueryMsg::GracePeriod {})
+            .unwrap();
+        assert_eq!(resp.seconds, 0);
+
+        app.update_block(|block| block.time = block.time.plus_seconds(delay));
+        for id in 7..=12 {
+            exec(&mut app, &stranger, msg::ExecuteMsg::ExecuteAction { id }).unwrap();
+        }
+        assert!(is_role(&app, INSPECTOR_ROLE, &stranger));
+        let resp: msg::GracePeriodResponse = app
+            .wrap()
+            .query_wasm_smart(addr.clone(), &msg::QueryMsg::GracePeriod {})
+            .unwrap();
+        assert_eq!(resp.seconds, 60);
     }
 }
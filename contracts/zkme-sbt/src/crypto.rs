@@ -0,0 +1,122 @@
+use bech32::{FromBase32, ToBase32};
+use cosmwasm_std::{Addr, Binary};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+/// Bech32 prefix of Sei accounts.
+pub const SEI_PREFIX: &str = "sei";
+
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+/// ADR-036 sign doc of `data` signed by `signer`, in the sorted amino JSON
+/// that wallets such as Keplr and Leap hash and sign in `signArbitrary`.
+pub fn adr036_sign_bytes(signer: &Addr, data: &[u8]) -> Vec<u8> {
+    format!(
+        r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"{}"}}}}],"sequence":"0"}}"#,
+        Binary::from(data).to_base64(),
+        signer
+    )
+    .into_bytes()
+}
+
+/// Salted commitment to a single disclosed KYC field.
+pub fn field_commitment(salt: &[u8], field: &str, value: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(field.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(value.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Leaf hash of an attribute claim such as `age_over_18=true`.
+pub fn merkle_leaf(leaf: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0u8]);
+    hasher.update(leaf.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Inner node over a sorted pair of children, so proofs need no position bits.
+pub fn merkle_node(a: &[u8], b: &[u8]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha256::new();
+    hasher.update([1u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Whether `leaf` is included under `root` given its sibling path `proof`.
+pub fn verify_merkle_proof(root: &[u8], leaf: &str, proof: &[impl AsRef<[u8]>]) -> bool {
+    let computed = proof.iter().fold(merkle_leaf(leaf), |node, sibling| {
+        merkle_node(&node, sibling.as_ref())
+    });
+    computed.as_slice() == root
+}
+
+/// Cosmos SDK account bytes of a compressed secp256k1 public key.
+pub fn pubkey_to_account(pubkey: &[u8]) -> [u8; 20] {
+    Ripemd160::digest(sha256(pubkey)).into()
+}
+
+/// Whether the bech32 `addr` is the account of `pubkey`, independent of the
+/// address prefix.
+pub fn is_pubkey_account(addr: &Addr, pubkey: &[u8]) -> bool {
+    bech32::decode(addr.as_str())
+        .ok()
+        .and_then(|(_hrp, data, _variant)| Vec::<u8>::from_base32(&data).ok())
+        .map(|data| data == pubkey_to_account(pubkey))
+        .unwrap_or(false)
+}
+
+/// Compressed form of an uncompressed (`0x04 || x || y`) secp256k1 public key.
+pub fn compress_pubkey(pubkey: &[u8]) -> Option<[u8; 33]> {
+    if pubkey.len() != 65 || pubkey[0] != 4 {
+        return None;
+    }
+    let mut compressed = [0u8; 33];
+    compressed[0] = if pubkey[64] & 1 == 0 { 2 } else { 3 };
+    compressed[1..].copy_from_slice(&pubkey[1..33]);
+    Some(compressed)
+}
+
+/// EVM address of an uncompressed secp256k1 public key: the last 20 bytes of
+/// `keccak256(x || y)`.
+pub fn evm_address(pubkey: &[u8]) -> Option<[u8; 20]> {
+    if pubkey.len() != 65 || pubkey[0] != 4 {
+        return None;
+    }
+    let hash = Keccak256::digest(&pubkey[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    Some(address)
+}
+
+/// Parses a `0x` prefixed hex EVM address, in any letter case.
+pub fn parse_evm_address(address: &str) -> Option<[u8; 20]> {
+    let digits = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))?;
+    let mut bytes = [0u8; 20];
+    hex::decode_to_slice(digits, &mut bytes).ok()?;
+    Some(bytes)
+}
+
+/// Lowercase `0x` prefixed form of an EVM address.
+pub fn format_evm_address(address: &[u8; 20]) -> String {
+    format!("0x{}", hex::encode(address))
+}
+
+/// Sei bech32 account of a compressed secp256k1 public key.
+pub fn sei_address(pubkey: &[u8]) -> Option<String> {
+    bech32::encode(
+        SEI_PREFIX,
+        pubkey_to_account(pubkey).to_base32(),
+        bech32::Variant::Bech32,
+    )
+    .ok()
+}
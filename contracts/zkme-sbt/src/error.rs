@@ -1,5 +1,5 @@
 use crate::state::TokenId;
-use cosmwasm_std::{Addr, StdError};
+use cosmwasm_std::{Addr, StdError, Timestamp};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -48,4 +48,142 @@ pub enum ContractError {
 
     #[error("{user} didn't approved to {cooperator}")]
     NoApprovementExist { cooperator: Addr, user: Addr },
+
+    #[error("too many items in one request, limit is {limit}")]
+    TooManyItems { limit: u32 },
+
+    #[error("{delegate} can't be a delegate")]
+    InvalidDelegate { delegate: Addr },
+
+    #[error("{owner} didn't grant {delegate} as delegate")]
+    NoDelegateExist { owner: Addr, delegate: Addr },
+
+    #[error("{account} is not a valid inspector")]
+    InvalidInspectorAccount { account: Addr },
+
+    #[error("{token_id} is frozen")]
+    FrozenToken { token_id: TokenId },
+
+    #[error("{token_id} is not frozen")]
+    NotFrozenToken { token_id: TokenId },
+
+    #[error("invalid permit")]
+    InvalidPermit {},
+
+    #[error("permit has expired")]
+    PermitExpired {},
+
+    #[error("permit {permit_name} was revoked")]
+    PermitRevoked { permit_name: String },
+
+    #[error("{signer} is not allowed to read this data")]
+    UnauthorizedPermit { signer: Addr },
+
+    #[error("commitments must be 32 byte SHA-256 digests")]
+    InvalidCommitment {},
+
+    #[error("invalid Groth16 verifying key")]
+    InvalidVerifyingKey {},
+
+    #[error("no verifying key registered for {question}")]
+    NonExistVerifyingKey { question: String },
+
+    #[error("invalid proof")]
+    InvalidProof {},
+
+    #[error("attestor key {key_id} already exists")]
+    AlreadyExistAttestorKey { key_id: String },
+
+    #[error("attestor key {key_id} is invalid or revoked")]
+    InvalidAttestorKey { key_id: String },
+
+    #[error("invalid attestation")]
+    InvalidAttestation {},
+
+    #[error("attestation nonce {nonce} was already used")]
+    InvalidAttestationNonce { nonce: u64 },
+
+    #[error("{account} is not a registered issuer")]
+    InvalidIssuerAccount { account: Addr },
+
+    #[error("{issuer} has no attestation on {token_id}")]
+    NonExistAttestation { token_id: TokenId, issuer: Addr },
+
+    #[error("tier {tier} doesn't exist")]
+    NonExistTier { tier: String },
+
+    #[error("renewed validity {validity} must be later than the current one")]
+    InvalidValidity { validity: Timestamp },
+
+    #[error("kyc data of {user} was erased at {erased_at}")]
+    ErasedData { user: Addr, erased_at: Timestamp },
+
+    #[error("{account} was recovered to another address")]
+    RecoveredAccount { account: Addr },
+
+    #[error("invalid guardians or threshold")]
+    InvalidGuardians {},
+
+    #[error("{account} is not a guardian of {token_id}")]
+    InvalidGuardianAccount { account: Addr, token_id: TokenId },
+
+    #[error("{token_id} has no pending recovery")]
+    NonExistRecovery { token_id: TokenId },
+
+    #[error("{token_id} already has a pending recovery")]
+    AlreadyExistRecovery { token_id: TokenId },
+
+    #[error("{guardian} already approved the recovery")]
+    AlreadyApprovedRecovery { guardian: Addr },
+
+    #[error("recovery of {token_id} is not executable yet")]
+    RecoveryNotReady { token_id: TokenId },
+
+    #[error("{address} is already linked to a token")]
+    AlreadyLinkedAddress { address: Addr },
+
+    #[error("{address} is not a linked address")]
+    NonExistLink { address: Addr },
+
+    #[error("invalid link proof")]
+    InvalidLinkProof {},
+
+    #[error("invalid evm address {address}")]
+    InvalidEvmAddress { address: String },
+
+    #[error("evm address {address} is not mapped to a sei account")]
+    NonExistEvmMapping { address: String },
+
+    #[error("invalid evm public key or signature")]
+    InvalidEvmPubkey {},
+
+    #[error("{address} is denied: {reason}")]
+    DeniedAddress { address: Addr, reason: String },
+
+    #[error("{code} is not an ISO 3166 jurisdiction code")]
+    InvalidJurisdiction { code: String },
+
+    #[error("{operation} is paused")]
+    Paused { operation: String },
+
+    #[error("{account} is not the contract owner")]
+    InvalidOwnerAccount { account: Addr },
+
+    #[error("{account} is not the pending contract owner")]
+    InvalidPendingOwner { account: Addr },
+
+    #[error("no ownership transfer is pending")]
+    NonExistOwnershipTransfer {},
+
+    #[error("ownership transfer expired")]
+    OwnershipTransferExpired {},
+
+    #[error("{account} owns the contract and must keep the admin role")]
+    CannotRevokeOwner { account: Addr },
+
+    #[error("queued action {id} doesn't exist")]
+    NonExistQueuedAction { id: u64 },
+
+    #[error("queued action {id} can't be executed before {eta}")]
+    TimelockNotReady { id: u64, eta: Timestamp },
 }
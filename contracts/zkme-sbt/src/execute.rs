@@ -1,29 +1,43 @@
 use crate::{
+    crypto,
     error::ContractError,
+    msg::{self, ApprovalRequest},
+    query,
     state::{self, TokenId},
+    verifier,
+};
+use cosmwasm_std::{
+    to_binary, to_vec, Addr, Binary, Deps, DepsMut, Env, Event, MessageInfo, Response, Storage,
+    Timestamp,
 };
-use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, Timestamp};
 
 pub(crate) fn grant_role(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     role: &str,
     user: Addr,
 ) -> Result<Response, ContractError> {
-    if state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)? {
-        state::grant_role(deps.storage, role, user.clone())?;
-    } else {
+    if !state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)? {
         return Err(ContractError::InvalidAdminAccount {
             account: info.sender,
         });
     }
-    let resp = Response::new()
-        .add_attribute("action", "grantRole")
-        .add_attribute("role", role)
-        .add_attribute("account", user);
-
-    Ok(resp)
+    let action = state::TimelockAction::GrantRole {
+        role: role.to_string(),
+        user,
+    };
+    if [
+        state::DEFAULT_ADMIN_ROLE,
+        state::OPERATOR_ROLE,
+        state::INSPECTOR_ROLE,
+    ]
+    .contains(&role)
+    {
+        apply_or_queue(deps.storage, &env, &info.sender, action)
+    } else {
+        apply_action(deps.storage, &env, &info.sender, action)
+    }
 }
 
 pub(crate) fn revoke_role(
@@ -33,6 +47,11 @@ pub(crate) fn revoke_role(
     role: &str,
     user: Addr,
 ) -> Result<Response, ContractError> {
+    if role == state::DEFAULT_ADMIN_ROLE
+        && state::get_ownership(deps.storage)?.owner.as_ref() == Some(&user)
+    {
+        return Err(ContractError::CannotRevokeOwner { account: user });
+    }
     if state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
         state::revoke_role(deps.storage, role, &user)?;
     } else {
@@ -51,174 +70,2307 @@ pub(crate) fn revoke_role(
 
 pub(crate) fn set_questions(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     cooperator: Addr,
     questions: Vec<String>,
 ) -> Result<Response, ContractError> {
-    if state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)?
-        && state::has_role(deps.storage, state::COOPERATOR_ROLE, &cooperator)?
+    if !state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)?
+        || !state::has_role(deps.storage, state::COOPERATOR_ROLE, &cooperator)?
+    {
+        return Err(ContractError::InvalidOperatorAccount {
+            account: info.sender,
+        });
+    }
+
+    apply_or_queue(
+        deps.storage,
+        &env,
+        &info.sender,
+        state::TimelockAction::SetQuestions {
+            cooperator,
+            questions,
+        },
+    )
+}
+
+pub(crate) fn set_policy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cooperator: Addr,
+    policy: state::VerifyPolicy,
+) -> Result<Response, ContractError> {
+    if let Some(min_tier) = &policy.min_tier {
+        if state::tier_rank(deps.storage, min_tier)?.is_none() {
+            return Err(ContractError::NonExistTier {
+                tier: min_tier.clone(),
+            });
+        }
+    }
+    if let Some(rule) = &policy.jurisdictions {
+        if let Some(code) = rule
+            .codes()
+            .iter()
+            .find(|code| !state::is_jurisdiction_code(code))
+        {
+            return Err(ContractError::InvalidJurisdiction { code: code.clone() });
+        }
+    }
+
+    if !state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)?
+        || !state::has_role(deps.storage, state::COOPERATOR_ROLE, &cooperator)?
     {
-        state::set_questions(deps.storage, &cooperator, questions)?;
-    } else {
         return Err(ContractError::InvalidOperatorAccount {
             account: info.sender,
         });
     }
 
+    apply_or_queue(
+        deps.storage,
+        &env,
+        &info.sender,
+        state::TimelockAction::SetPolicy { cooperator, policy },
+    )
+}
+
+pub(crate) fn set_tiers(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tiers: Vec<String>,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+
+    apply_or_queue(
+        deps.storage,
+        &env,
+        &info.sender,
+        state::TimelockAction::SetTiers { tiers },
+    )
+}
+
+pub(crate) fn set_tier(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    tier: String,
+) -> Result<Response, ContractError> {
+    state::ensure_not_paused(deps.storage, |flags| flags.kyc_writes, "kyc writes")?;
+    if !state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidOperatorAccount {
+            account: info.sender,
+        });
+    }
+    if !state::has_owner(deps.storage, &token_id)? {
+        return Err(ContractError::InvalidTokenId { token_id });
+    }
+    if state::tier_rank(deps.storage, &tier)?.is_none() {
+        return Err(ContractError::NonExistTier { tier });
+    }
+
+    state::push_operator_action(
+        deps.storage,
+        &token_id,
+        "setTier",
+        &info.sender,
+        env.block.time,
+    )?;
+    state::save_token_tier(deps.storage, &token_id, &tier)?;
+
     let resp = Response::new()
-        .add_attribute("action", "setQuestions")
-        .add_attribute("cooperator", cooperator);
+        .add_attribute("action", "setTier")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("tier", tier);
 
     Ok(resp)
 }
 
-pub(crate) fn attest(
+/// Records the jurisdiction of a token holder. The code is public in the
+/// message body; the response leaves it out only to keep it out of events.
+pub(crate) fn set_jurisdiction(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    to: Addr,
+    token_id: TokenId,
+    jurisdiction: String,
 ) -> Result<Response, ContractError> {
-    if state::has_token(deps.storage, &to) {
-        return Err(ContractError::AlreadyMintedToken);
+    state::ensure_not_paused(deps.storage, |flags| flags.kyc_writes, "kyc writes")?;
+    if !state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidOperatorAccount {
+            account: info.sender,
+        });
+    }
+    if !state::has_owner(deps.storage, &token_id)? {
+        return Err(ContractError::InvalidTokenId { token_id });
+    }
+    if !state::is_jurisdiction_code(&jurisdiction) {
+        return Err(ContractError::InvalidJurisdiction { code: jurisdiction });
     }
 
-    let token_id = state::get_token_id(deps.storage)?;
-    state::save_owner(deps.storage, &token_id, &to)?;
-    state::save_token(deps.storage, &to, &token_id)?;
-    state::increase_counter(deps.storage)?;
+    state::save_token_jurisdiction(deps.storage, &token_id, &jurisdiction)?;
+    state::push_operator_action(
+        deps.storage,
+        &token_id,
+        "setJurisdiction",
+        &info.sender,
+        env.block.time,
+    )?;
 
     let resp = Response::new()
-        .add_attribute("action", "attest")
-        .add_attribute("payer", info.sender)
-        .add_attribute("to", to)
+        .add_attribute("action", "setJurisdiction")
         .add_attribute("tokenId", token_id.to_string());
 
     Ok(resp)
 }
 
-pub(crate) fn set_kyc_data(
+pub(crate) fn set_age_claims(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    over: Vec<u8>,
+) -> Result<Response, ContractError> {
+    state::ensure_not_paused(deps.storage, |flags| flags.kyc_writes, "kyc writes")?;
+    if !state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidOperatorAccount {
+            account: info.sender,
+        });
+    }
+    if !state::has_owner(deps.storage, &token_id)? {
+        return Err(ContractError::InvalidTokenId { token_id });
+    }
+
+    let mut thresholds = over;
+    thresholds.sort_unstable();
+    thresholds.dedup();
+    state::save_age_claims(deps.storage, &token_id, &thresholds)?;
+    state::push_operator_action(
+        deps.storage,
+        &token_id,
+        "setAgeClaims",
+        &info.sender,
+        env.block.time,
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "setAgeClaims")
+        .add_attribute("tokenId", token_id.to_string());
+
+    Ok(resp)
+}
+
+pub(crate) fn register_issuer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    issuer: Addr,
+    name: String,
+    trust_level: u8,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+
+    apply_or_queue(
+        deps.storage,
+        &env,
+        &info.sender,
+        state::TimelockAction::RegisterIssuer {
+            issuer,
+            name,
+            trust_level,
+        },
+    )
+}
+
+pub(crate) fn remove_issuer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    issuer: Addr,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+
+    apply_or_queue(
+        deps.storage,
+        &env,
+        &info.sender,
+        state::TimelockAction::RemoveIssuer { issuer },
+    )
+}
+
+pub(crate) fn issue_attestation(
+    deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     token_id: TokenId,
-    key: String,
     validity: Timestamp,
     data: String,
     questions: Vec<String>,
 ) -> Result<Response, ContractError> {
-    if state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)? {
-        if state::has_owner(deps.storage, &token_id)? {
-            state::save_kyc(
-                deps.storage,
-                &token_id,
-                state::KycData {
-                    key,
-                    validity,
-                    data,
-                    questions,
-                },
-            )?;
-        } else {
-            return Err(ContractError::InvalidTokenId { token_id });
-        }
-    } else {
-        return Err(ContractError::InvalidOperatorAccount {
+    state::ensure_not_paused(deps.storage, |flags| flags.kyc_writes, "kyc writes")?;
+    if state::get_issuer(deps.storage, &info.sender)?.is_none() {
+        return Err(ContractError::InvalidIssuerAccount {
             account: info.sender,
         });
     }
+    if !state::has_owner(deps.storage, &token_id)? {
+        return Err(ContractError::InvalidTokenId { token_id });
+    }
+
+    state::save_issuer_attestation(
+        deps.storage,
+        token_id,
+        &info.sender,
+        &state::IssuerAttestation {
+            validity,
+            data,
+            questions,
+            issued_at: env.block.time,
+        },
+    )?;
 
     let resp = Response::new()
-        .add_attribute("action", "setKycData")
+        .add_attribute("action", "issueAttestation")
+        .add_attribute("issuer", info.sender)
         .add_attribute("tokenId", token_id.to_string());
 
     Ok(resp)
 }
 
-pub(crate) fn set_token_base_uri(
+pub(crate) fn withdraw_attestation(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    uri: String,
+    token_id: TokenId,
 ) -> Result<Response, ContractError> {
-    if state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
-        state::set_token_base_uri(deps.storage, uri.clone())?;
-    } else {
-        return Err(ContractError::InvalidAdminAccount {
+    if !state::has_issuer_attestation(deps.storage, token_id, &info.sender) {
+        return Err(ContractError::NonExistAttestation {
+            token_id,
+            issuer: info.sender,
+        });
+    }
+    state::remove_issuer_attestation(deps.storage, token_id, &info.sender)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "withdrawAttestation")
+        .add_attribute("issuer", info.sender)
+        .add_attribute("tokenId", token_id.to_string());
+
+    Ok(resp)
+}
+
+/// Operator-driven recovery of a lost wallet: moves the SBT, and with it the
+/// KYC data, to `new_owner`.
+pub(crate) fn recover_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    new_owner: Addr,
+    move_approvals: bool,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidOperatorAccount {
             account: info.sender,
         });
     }
+    let old_owner = recover(
+        deps,
+        &env,
+        &info.sender,
+        token_id,
+        &new_owner,
+        move_approvals,
+    )?;
 
     let resp = Response::new()
-        .add_attribute("action", "setTokenBaseUri")
-        .add_attribute("newUri", uri);
+        .add_attribute("action", "recoverToken")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("oldOwner", old_owner)
+        .add_attribute("newOwner", new_owner)
+        .add_attribute("moveApprovals", move_approvals.to_string());
+
     Ok(resp)
 }
 
-pub(crate) fn approve(
+pub(crate) fn set_guardians(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    cooperator: Addr,
-    token_id: TokenId,
-    cooperator_key: String,
+    guardians: Vec<Addr>,
+    threshold: u32,
 ) -> Result<Response, ContractError> {
-    let owner = state::get_owner(deps.storage, &token_id)?
-        .ok_or(ContractError::InvalidTokenId { token_id })?;
-    if owner != info.sender {
-        return Err(ContractError::InvalidOwner { token_id, owner });
+    let owner = info.sender;
+    let token_id = state::get_token(deps.storage, &owner)?.ok_or(ContractError::NoSBTExist {
+        user: owner.clone(),
+    })?;
+    let limit = state::get_guardian_limit(deps.storage)?;
+    if guardians.len() > limit as usize {
+        return Err(ContractError::TooManyItems { limit });
     }
 
-    if state::has_role(deps.storage, state::COOPERATOR_ROLE, &cooperator)? {
-        state::save_pu(deps.storage, &cooperator, &owner, token_id)?;
-        state::save_approved(deps.storage, &cooperator, token_id)?;
-        state::save_approved_kyc_data(deps.storage, &cooperator, token_id, cooperator_key)?;
+    let mut unique = guardians.clone();
+    unique.sort();
+    unique.dedup();
+    let valid = unique.len() == guardians.len()
+        && !guardians.contains(&owner)
+        && threshold as usize <= guardians.len()
+        && (threshold > 0 || guardians.is_empty());
+    if !valid {
+        return Err(ContractError::InvalidGuardians {});
+    }
+
+    // a new guardian set invalidates any recovery in flight
+    state::remove_pending_recovery(deps.storage, &token_id);
+    if guardians.is_empty() {
+        state::remove_guardians(deps.storage, &token_id);
     } else {
-        return Err(ContractError::InvalidCooperatorAccount {
-            account: cooperator,
+        state::save_guardians(
+            deps.storage,
+            &token_id,
+            &state::GuardianConfig {
+                guardians: guardians.clone(),
+                threshold,
+            },
+        )?;
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "setGuardians")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("guardians", guardians.len().to_string())
+        .add_attribute("threshold", threshold.to_string());
+
+    Ok(resp)
+}
+
+pub(crate) fn propose_recovery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    new_owner: Addr,
+) -> Result<Response, ContractError> {
+    let config = guardian_config(deps.storage, &info.sender, token_id)?;
+    if state::get_pending_recovery(deps.storage, &token_id)?.is_some() {
+        return Err(ContractError::AlreadyExistRecovery { token_id });
+    }
+    if state::has_token(deps.storage, &new_owner) {
+        return Err(ContractError::AlreadyMintedToken);
+    }
+
+    let recovery = state::PendingRecovery {
+        new_owner: new_owner.clone(),
+        proposed_by: info.sender.clone(),
+        proposed_at: env.block.time,
+        approvals: vec![info.sender.clone()],
+        approved_at: (config.threshold <= 1).then_some(env.block.time),
+    };
+    state::save_pending_recovery(deps.storage, &token_id, &recovery)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "proposeRecovery")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("guardian", info.sender)
+        .add_attribute("newOwner", new_owner);
+
+    Ok(resp)
+}
+
+pub(crate) fn approve_recovery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+) -> Result<Response, ContractError> {
+    let config = guardian_config(deps.storage, &info.sender, token_id)?;
+    let mut recovery = state::get_pending_recovery(deps.storage, &token_id)?
+        .ok_or(ContractError::NonExistRecovery { token_id })?;
+    if recovery.approvals.contains(&info.sender) {
+        return Err(ContractError::AlreadyApprovedRecovery {
+            guardian: info.sender,
         });
     }
 
+    recovery.approvals.push(info.sender.clone());
+    if recovery.approved_at.is_none() && recovery.approvals.len() >= config.threshold as usize {
+        recovery.approved_at = Some(env.block.time);
+    }
+    state::save_pending_recovery(deps.storage, &token_id, &recovery)?;
+
     let resp = Response::new()
-        .add_attribute("action", "approve")
-        .add_attribute("cooperator", cooperator)
+        .add_attribute("action", "approveRecovery")
         .add_attribute("tokenId", token_id.to_string())
-        .add_attribute("user", owner);
+        .add_attribute("guardian", info.sender)
+        .add_attribute("approvals", recovery.approvals.len().to_string());
 
     Ok(resp)
 }
 
-pub(crate) fn revoke(
+/// Lets the current owner, or any guardian, call off a pending recovery.
+pub(crate) fn cancel_recovery(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
-    cooperator: Addr,
     token_id: TokenId,
 ) -> Result<Response, ContractError> {
     let owner = state::get_owner(deps.storage, &token_id)?
         .ok_or(ContractError::InvalidTokenId { token_id })?;
     if owner != info.sender {
-        return Err(ContractError::InvalidOwner { token_id, owner });
+        guardian_config(deps.storage, &info.sender, token_id)?;
+    }
+    if state::get_pending_recovery(deps.storage, &token_id)?.is_none() {
+        return Err(ContractError::NonExistRecovery { token_id });
     }
+    state::remove_pending_recovery(deps.storage, &token_id);
 
-    if state::has_role(deps.storage, state::COOPERATOR_ROLE, &cooperator)? {
-        if state::has_approve(deps.storage, &cooperator, &owner) {
-            state::remove_pu(deps.storage, &cooperator, &owner);
-            state::remove_approved(deps.storage, &cooperator, token_id)?;
-        } else {
-            return Err(ContractError::InvalidRevokeFromCooperator { owner, cooperator });
-        }
-    } else {
-        return Err(ContractError::InvalidCooperatorAccount {
-            account: cooperator,
-        });
+    let resp = Response::new()
+        .add_attribute("action", "cancelRecovery")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("canceledBy", info.sender);
+
+    Ok(resp)
+}
+
+/// Completes a guardian-approved recovery once the recovery delay has passed.
+/// Anyone may call this.
+pub(crate) fn execute_recovery(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+) -> Result<Response, ContractError> {
+    let recovery = state::get_pending_recovery(deps.storage, &token_id)?
+        .ok_or(ContractError::NonExistRecovery { token_id })?;
+    let delay = state::get_recovery_delay(deps.storage)?;
+    let ready = recovery
+        .approved_at
+        .is_some_and(|approved_at| approved_at.plus_seconds(delay) <= env.block.time);
+    if !ready {
+        return Err(ContractError::RecoveryNotReady { token_id });
+    }
+    if state::is_frozen(deps.storage, &token_id) {
+        return Err(ContractError::FrozenToken { token_id });
     }
 
+    let old_owner = recover(
+        deps,
+        &env,
+        &info.sender,
+        token_id,
+        &recovery.new_owner,
+        true,
+    )?;
+
     let resp = Response::new()
-        .add_attribute("action", "revoke")
-        .add_attribute("cooperator", cooperator)
+        .add_attribute("action", "executeRecovery")
         .add_attribute("tokenId", token_id.to_string())
-        .add_attribute("user", owner);
+        .add_attribute("oldOwner", old_owner)
+        .add_attribute("newOwner", recovery.new_owner);
+
+    Ok(resp)
+}
+
+pub(crate) fn set_recovery_delay(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    seconds: u64,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+    state::set_recovery_delay(deps.storage, seconds)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "setRecoveryDelay")
+        .add_attribute("seconds", seconds.to_string());
+    Ok(resp)
+}
+
+pub(crate) fn set_guardian_limit(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    limit: u32,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+    state::set_guardian_limit(deps.storage, limit)?;
 
+    let resp = Response::new()
+        .add_attribute("action", "setGuardianLimit")
+        .add_attribute("limit", limit.to_string());
     Ok(resp)
 }
+
+/// The guardian set of `token_id`, provided `sender` is one of its guardians.
+fn guardian_config(
+    storage: &dyn Storage,
+    sender: &Addr,
+    token_id: TokenId,
+) -> Result<state::GuardianConfig, ContractError> {
+    state::get_guardians(storage, &token_id)?
+        .filter(|config| config.guardians.contains(sender))
+        .ok_or(ContractError::InvalidGuardianAccount {
+            account: sender.clone(),
+            token_id,
+        })
+}
+
+/// Moves `token_id` to `new_owner` and marks the old owner as recovered.
+/// Approvals follow the token when `move_approvals` is set and are revoked
+/// otherwise, and any guardian recovery in flight is dropped. Callers are
+/// responsible for authorizing `recovered_by`.
+fn recover(
+    deps: DepsMut,
+    env: &Env,
+    recovered_by: &Addr,
+    token_id: TokenId,
+    new_owner: &Addr,
+    move_approvals: bool,
+) -> Result<Addr, ContractError> {
+    state::ensure_not_paused(deps.storage, |flags| flags.kyc_writes, "kyc writes")?;
+    ensure_not_denied(deps.as_ref(), new_owner)?;
+    let storage = deps.storage;
+    let old_owner =
+        state::get_owner(storage, &token_id)?.ok_or(ContractError::InvalidTokenId { token_id })?;
+    if state::has_token(storage, new_owner) {
+        return Err(ContractError::AlreadyMintedToken);
+    }
+    if state::is_recovered(storage, new_owner) {
+        return Err(ContractError::RecoveredAccount {
+            account: new_owner.clone(),
+        });
+    }
+    // recovering to one of the token's own linked wallets promotes it
+    match state::get_linked_token(storage, new_owner)? {
+        Some(linked) if linked == token_id => state::remove_link(storage, token_id, new_owner),
+        Some(_) => {
+            return Err(ContractError::AlreadyLinkedAddress {
+                address: new_owner.clone(),
+            })
+        }
+        None => {}
+    }
+
+    for (cooperator, approval) in
+        state::list_approvals_by_user(storage, &old_owner, None, usize::MAX)?
+    {
+        if move_approvals {
+            state::remove_pu(storage, &cooperator, &old_owner);
+            state::remove_approval(storage, &cooperator, &old_owner)?;
+            state::save_pu(storage, &cooperator, new_owner, token_id)?;
+            state::save_approval(
+                storage,
+                &cooperator,
+                new_owner,
+                token_id,
+                approval.approved_at,
+            )?;
+        } else {
+            revoke_cooperator(storage, &old_owner, &cooperator, token_id)?;
+        }
+    }
+
+    state::transfer_token(storage, &token_id, &old_owner, new_owner)?;
+    state::remove_pending_recovery(storage, &token_id);
+    state::remove_erasure(storage, new_owner);
+    state::save_recovery(
+        storage,
+        &old_owner,
+        &state::Recovery {
+            token_id,
+            new_owner: new_owner.clone(),
+            recovered_by: recovered_by.clone(),
+            recovered_at: env.block.time,
+        },
+    )?;
+    state::push_operator_action(
+        storage,
+        &token_id,
+        "recoverToken",
+        recovered_by,
+        env.block.time,
+    )?;
+
+    Ok(old_owner)
+}
+
+/// Links another wallet of the sender to their token. The linked wallet
+/// proves control by signing `LinkParams`, see `msg::LinkParams`.
+pub(crate) fn link_address(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+    pubkey: Binary,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    state::ensure_not_paused(deps.storage, |flags| flags.kyc_writes, "kyc writes")?;
+    let owner = info.sender;
+    let token_id = state::get_token(deps.storage, &owner)?.ok_or(ContractError::NoSBTExist {
+        user: owner.clone(),
+    })?;
+    if state::has_token(deps.storage, &address) {
+        return Err(ContractError::AlreadyMintedToken);
+    }
+    if state::is_linked(deps.storage, &address) {
+        return Err(ContractError::AlreadyLinkedAddress { address });
+    }
+    if state::is_recovered(deps.storage, &address) {
+        return Err(ContractError::RecoveredAccount { account: address });
+    }
+    ensure_not_denied(deps.as_ref(), &address)?;
+    let limit = state::get_link_limit(deps.storage)?;
+    if state::list_links(deps.storage, token_id)?.len() >= limit as usize {
+        return Err(ContractError::TooManyItems { limit });
+    }
+
+    let params = msg::LinkParams {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.clone(),
+        token_id,
+        address: address.clone(),
+    };
+    let hash = crypto::sha256(&crypto::adr036_sign_bytes(&address, &to_vec(&params)?));
+    let verified = crypto::is_pubkey_account(&address, &pubkey)
+        && deps
+            .api
+            .secp256k1_verify(&hash, &signature, &pubkey)
+            .unwrap_or(false);
+    if !verified {
+        return Err(ContractError::InvalidLinkProof {});
+    }
+
+    state::save_link(deps.storage, token_id, &address, env.block.time)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "linkAddress")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("user", owner)
+        .add_attribute("address", address);
+
+    Ok(resp)
+}
+
+/// Removes a linked wallet, either by the token owner or by the wallet itself.
+pub(crate) fn unlink_address(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    let token_id =
+        state::get_linked_token(deps.storage, &address)?.ok_or(ContractError::NonExistLink {
+            address: address.clone(),
+        })?;
+    let owner = state::get_owner(deps.storage, &token_id)?
+        .ok_or(ContractError::InvalidTokenId { token_id })?;
+    if info.sender != owner && info.sender != address {
+        return Err(ContractError::InvalidOwner { token_id, owner });
+    }
+    state::remove_link(deps.storage, token_id, &address);
+
+    let resp = Response::new()
+        .add_attribute("action", "unlinkAddress")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("address", address);
+
+    Ok(resp)
+}
+
+pub(crate) fn set_link_limit(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    limit: u32,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+    state::set_link_limit(deps.storage, limit)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "setLinkLimit")
+        .add_attribute("limit", limit.to_string());
+    Ok(resp)
+}
+
+/// Maps the sender to the EVM address of the same secp256k1 key. The
+/// uncompressed `pubkey` must derive the sender and sign `EvmLinkParams`.
+pub(crate) fn register_evm_address(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pubkey: Binary,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let compressed = crypto::compress_pubkey(&pubkey).ok_or(ContractError::InvalidEvmPubkey {})?;
+    if !crypto::is_pubkey_account(&info.sender, &compressed) {
+        return Err(ContractError::InvalidEvmPubkey {});
+    }
+    let params = msg::EvmLinkParams {
+        chain_id: env.block.chain_id,
+        contract: env.contract.address,
+        address: info.sender.clone(),
+    };
+    let hash = crypto::sha256(&crypto::adr036_sign_bytes(&info.sender, &to_vec(&params)?));
+    if !deps
+        .api
+        .secp256k1_verify(&hash, &signature, &pubkey)
+        .unwrap_or(false)
+    {
+        return Err(ContractError::InvalidEvmPubkey {});
+    }
+
+    let evm_address = crypto::evm_address(&pubkey)
+        .map(|address| crypto::format_evm_address(&address))
+        .ok_or(ContractError::InvalidEvmPubkey {})?;
+    state::save_evm_address(deps.storage, &info.sender, &evm_address)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "registerEvmAddress")
+        .add_attribute("user", info.sender)
+        .add_attribute("evmAddress", evm_address);
+
+    Ok(resp)
+}
+
+pub(crate) fn add_to_denylist(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+    reason: String,
+) -> Result<Response, ContractError> {
+    ensure_inspector_or_operator(deps.storage, &info.sender)?;
+    state::save_deny_entry(
+        deps.storage,
+        &address,
+        &state::DenyEntry {
+            reason: reason.clone(),
+            added_by: info.sender.clone(),
+            added_at: env.block.time,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "addToDenylist")
+        .add_attribute("operator", info.sender)
+        .add_attribute("address", address)
+        .add_attribute("reason", reason);
+
+    Ok(resp)
+}
+
+pub(crate) fn remove_from_denylist(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    ensure_inspector_or_operator(deps.storage, &info.sender)?;
+    state::remove_deny_entry(deps.storage, &address);
+
+    let resp = Response::new()
+        .add_attribute("action", "removeFromDenylist")
+        .add_attribute("operator", info.sender)
+        .add_attribute("address", address);
+
+    Ok(resp)
+}
+
+pub(crate) fn set_screening_contract(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    contract: Option<Addr>,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+    state::set_screening_contract(deps.storage, contract.as_ref())?;
+
+    let resp = Response::new()
+        .add_attribute("action", "setScreeningContract")
+        .add_attribute("contract", contract.map(String::from).unwrap_or_default());
+    Ok(resp)
+}
+
+/// Wipes the sender's KYC data with its history, tier and verified claims,
+/// every cooperator copy of it and issuer attestations, revokes all
+/// approvals, optionally burns the SBT, and leaves an erasure tombstone.
+pub(crate) fn erase_my_data(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    keep_token: bool,
+) -> Result<Response, ContractError> {
+    let owner = info.sender;
+    let token_id = state::get_token(deps.storage, &owner)?.ok_or(ContractError::NoSBTExist {
+        user: owner.clone(),
+    })?;
+    if state::is_frozen(deps.storage, &token_id) {
+        return Err(ContractError::FrozenToken { token_id });
+    }
+
+    state::remove_kyc(deps.storage, &token_id)?;
+    state::remove_kyc_commitment(deps.storage, &token_id);
+    state::remove_attribute_root(deps.storage, &token_id);
+    state::remove_age_claims(deps.storage, &token_id);
+    state::remove_token_jurisdiction(deps.storage, &token_id);
+    state::remove_token_tier(deps.storage, &token_id);
+    state::remove_verified_claims(deps.storage, token_id)?;
+    // the history repeats the questions and validity of every write
+    state::remove_kyc_history(deps.storage, &token_id);
+    let cooperators = state::remove_approved_kyc_copies(deps.storage, token_id)?;
+    // consent is meaningless once the data it covers is gone
+    for (cooperator, _) in state::list_approvals_by_user(deps.storage, &owner, None, usize::MAX)? {
+        revoke_cooperator(deps.storage, &owner, &cooperator, token_id)?;
+    }
+    for (issuer, _) in state::list_issuer_attestations(deps.storage, token_id)? {
+        state::remove_issuer_attestation(deps.storage, token_id, &issuer)?;
+    }
+    if !keep_token {
+        for (address, _) in state::list_links(deps.storage, token_id)? {
+            state::remove_link(deps.storage, token_id, &address);
+        }
+        // a kept token keeps its nonce so old attestations can't be replayed
+        state::remove_attestation_nonce(deps.storage, &token_id);
+        state::burn_token(deps.storage, &owner, &token_id)?;
+    }
+    state::save_erasure(
+        deps.storage,
+        &owner,
+        &state::Erasure {
+            token_id,
+            erased_at: env.block.time,
+            token_kept: keep_token,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "eraseMyData")
+        .add_attribute("user", owner)
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("tokenKept", keep_token.to_string())
+        .add_attribute("copies", cooperators.len().to_string());
+
+    Ok(resp)
+}
+
+pub(crate) fn attest(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    to: Addr,
+) -> Result<Response, ContractError> {
+    state::ensure_not_paused(deps.storage, |flags| flags.attest, "attest")?;
+    if state::has_token(deps.storage, &to) {
+        return Err(ContractError::AlreadyMintedToken);
+    }
+    if state::is_recovered(deps.storage, &to) {
+        return Err(ContractError::RecoveredAccount { account: to });
+    }
+    if state::is_linked(deps.storage, &to) {
+        return Err(ContractError::AlreadyLinkedAddress { address: to });
+    }
+    ensure_not_denied(deps.as_ref(), &to)?;
+
+    let token_id = state::get_token_id(deps.storage)?;
+    state::save_owner(deps.storage, &token_id, &to)?;
+    state::save_token(deps.storage, &to, &token_id)?;
+    state::increase_counter(deps.storage)?;
+    state::remove_erasure(deps.storage, &to);
+
+    let resp = Response::new()
+        .add_attribute("action", "attest")
+        .add_attribute("payer", info.sender)
+        .add_attribute("to", to)
+        .add_attribute("tokenId", token_id.to_string());
+
+    Ok(resp)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn set_kyc_data(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    key: String,
+    validity: Timestamp,
+    data: String,
+    questions: Vec<String>,
+) -> Result<Response, ContractError> {
+    write_kyc(
+        deps.storage,
+        &env,
+        &info.sender,
+        token_id,
+        "setKycData",
+        state::KycData {
+            key,
+            validity,
+            data,
+            questions,
+        },
+    )?;
+    state::remove_kyc_commitment(deps.storage, &token_id);
+
+    let resp = Response::new()
+        .add_attribute("action", "setKycData")
+        .add_attribute("tokenId", token_id.to_string());
+
+    Ok(resp)
+}
+
+/// Extends the validity of a token's KYC data, leaving the payload untouched.
+pub(crate) fn renew_kyc(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    validity: Timestamp,
+) -> Result<Response, ContractError> {
+    let kyc =
+        state::get_kyc(deps.storage, &token_id)?.ok_or(ContractError::NonExistKyc { token_id })?;
+    if validity <= kyc.validity {
+        return Err(ContractError::InvalidValidity { validity });
+    }
+
+    write_kyc(
+        deps.storage,
+        &env,
+        &info.sender,
+        token_id,
+        "renewKyc",
+        state::KycData { validity, ..kyc },
+    )?;
+    let cooperators = state::renew_approved_kyc_copies(deps.storage, token_id, validity)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "renewKyc")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("validity", validity.to_string())
+        .add_attribute("copies", cooperators.len().to_string());
+
+    Ok(resp)
+}
+
+/// Privacy mode variant of `set_kyc_data`: the attribute payload is kept off
+/// chain and only its commitments are stored.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn set_kyc_commitment(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    key: String,
+    validity: Timestamp,
+    questions: Vec<String>,
+    commitment: state::KycCommitment,
+) -> Result<Response, ContractError> {
+    let digests = std::iter::once(&commitment.data_commitment).chain(
+        commitment
+            .field_commitments
+            .iter()
+            .map(|field| &field.commitment),
+    );
+    for digest in digests {
+        if digest.len() != 32 {
+            return Err(ContractError::InvalidCommitment {});
+        }
+    }
+
+    write_kyc(
+        deps.storage,
+        &env,
+        &info.sender,
+        token_id,
+        "setKycCommitment",
+        state::KycData {
+            key,
+            validity,
+            data: String::new(),
+            questions,
+        },
+    )?;
+    state::save_kyc_commitment(deps.storage, &token_id, &commitment)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "setKycCommitment")
+        .add_attribute("tokenId", token_id.to_string());
+
+    Ok(resp)
+}
+
+pub(crate) fn set_attribute_root(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    root: Binary,
+) -> Result<Response, ContractError> {
+    state::ensure_not_paused(deps.storage, |flags| flags.kyc_writes, "kyc writes")?;
+    if !state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidOperatorAccount {
+            account: info.sender,
+        });
+    }
+    if !state::has_owner(deps.storage, &token_id)? {
+        return Err(ContractError::InvalidTokenId { token_id });
+    }
+    if root.len() != 32 {
+        return Err(ContractError::InvalidCommitment {});
+    }
+
+    state::push_operator_action(
+        deps.storage,
+        &token_id,
+        "setAttributeRoot",
+        &info.sender,
+        env.block.time,
+    )?;
+    state::save_attribute_root(deps.storage, &token_id, &root)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "setAttributeRoot")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("root", root.to_base64());
+
+    Ok(resp)
+}
+
+pub(crate) fn register_verifying_key(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    question: String,
+    vk: Binary,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::OPERATOR_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidOperatorAccount {
+            account: info.sender,
+        });
+    }
+    verifier::parse_verifying_key(&vk)?;
+    // claims proven under an earlier key stop counting
+    let version = state::save_verifying_key(deps.storage, &question, &vk)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "registerVerifyingKey")
+        .add_attribute("question", question)
+        .add_attribute("version", version.to_string());
+
+    Ok(resp)
+}
+
+pub(crate) fn submit_proof(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    question: String,
+    proof: Binary,
+    public_inputs: Vec<Binary>,
+) -> Result<Response, ContractError> {
+    state::ensure_not_paused(deps.storage, |flags| flags.kyc_writes, "kyc writes")?;
+    let owner = state::get_owner(deps.storage, &token_id)?
+        .ok_or(ContractError::InvalidTokenId { token_id })?;
+    if owner != info.sender && !state::has_role(deps.storage, state::COOPERATOR_ROLE, &info.sender)?
+    {
+        return Err(ContractError::InvalidOwner { token_id, owner });
+    }
+
+    let vk = state::get_verifying_key(deps.storage, &question)?.ok_or(
+        ContractError::NonExistVerifyingKey {
+            question: question.clone(),
+        },
+    )?;
+    let key_version = state::get_verifying_key_version(deps.storage, &question)?;
+    let verified = verifier::verify_proof(
+        &vk,
+        &env.block.chain_id,
+        &env.contract.address,
+        token_id,
+        &proof,
+        &public_inputs,
+    )?;
+    if !verified {
+        return Err(ContractError::InvalidProof {});
+    }
+
+    state::save_verified_claim(
+        deps.storage,
+        token_id,
+        &question,
+        &state::VerifiedClaim {
+            submitter: info.sender.clone(),
+            verified_at: env.block.time,
+            key_version,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "submitProof")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("question", question)
+        .add_attribute("submitter", info.sender);
+
+    Ok(resp)
+}
+
+fn write_kyc(
+    storage: &mut dyn Storage,
+    env: &Env,
+    operator: &Addr,
+    token_id: TokenId,
+    action: &str,
+    kyc: state::KycData,
+) -> Result<(), ContractError> {
+    if !state::has_role(storage, state::OPERATOR_ROLE, operator)? {
+        return Err(ContractError::InvalidOperatorAccount {
+            account: operator.clone(),
+        });
+    }
+    store_kyc(storage, env, operator, token_id, action, kyc)
+}
+
+/// Stores KYC data for an existing token and records it in the token history.
+/// Callers are responsible for authorizing `updated_by`.
+fn store_kyc(
+    storage: &mut dyn Storage,
+    env: &Env,
+    updated_by: &Addr,
+    token_id: TokenId,
+    action: &str,
+    kyc: state::KycData,
+) -> Result<(), ContractError> {
+    state::ensure_not_paused(storage, |flags| flags.kyc_writes, "kyc writes")?;
+    let owner =
+        state::get_owner(storage, &token_id)?.ok_or(ContractError::InvalidTokenId { token_id })?;
+    state::remove_erasure(storage, &owner);
+
+    state::push_kyc_history(
+        storage,
+        &token_id,
+        state::KycMetadata {
+            updated_by: updated_by.clone(),
+            updated_at: env.block.time,
+            validity: kyc.validity,
+            questions: kyc.questions.clone(),
+        },
+    )?;
+    state::push_operator_action(storage, &token_id, action, updated_by, env.block.time)?;
+    state::save_kyc(storage, &token_id, kyc)
+}
+
+pub(crate) fn add_attestor_key(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    key_id: String,
+    algorithm: state::KeyAlgorithm,
+    pubkey: Binary,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+    if state::get_attestor_key(deps.storage, &key_id)?.is_some() {
+        return Err(ContractError::AlreadyExistAttestorKey { key_id });
+    }
+    let valid_length = match algorithm {
+        state::KeyAlgorithm::Secp256k1 => pubkey.len() == 33 || pubkey.len() == 65,
+        state::KeyAlgorithm::Ed25519 => pubkey.len() == 32,
+    };
+    if !valid_length {
+        return Err(ContractError::InvalidAttestorKey { key_id });
+    }
+
+    apply_or_queue(
+        deps.storage,
+        &env,
+        &info.sender,
+        state::TimelockAction::AddAttestorKey {
+            key_id,
+            algorithm,
+            pubkey,
+        },
+    )
+}
+
+pub(crate) fn revoke_attestor_key(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    key_id: String,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+    let mut key = state::get_attestor_key(deps.storage, &key_id)?.ok_or(
+        ContractError::InvalidAttestorKey {
+            key_id: key_id.clone(),
+        },
+    )?;
+    key.revoked_at.get_or_insert(env.block.time);
+    state::save_attestor_key(deps.storage, &key_id, &key)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "revokeAttestorKey")
+        .add_attribute("keyId", key_id);
+
+    Ok(resp)
+}
+
+/// Stores KYC data signed off chain by a registered attestor key, so the
+/// holder can submit it without an operator transaction.
+pub(crate) fn submit_attestation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    attestation: msg::Attestation,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let key = state::get_attestor_key(deps.storage, &attestation.key_id)?
+        .filter(|key| key.revoked_at.is_none())
+        .ok_or(ContractError::InvalidAttestorKey {
+            key_id: attestation.key_id.clone(),
+        })?;
+
+    if attestation.chain_id != env.block.chain_id || attestation.contract != env.contract.address {
+        return Err(ContractError::InvalidAttestation {});
+    }
+    let token_id = attestation.token_id;
+    let last_nonce = state::get_attestation_nonce(deps.storage, &token_id)?;
+    if last_nonce.is_some_and(|last_nonce| attestation.nonce <= last_nonce) {
+        return Err(ContractError::InvalidAttestationNonce {
+            nonce: attestation.nonce,
+        });
+    }
+
+    let hash = crypto::sha256(&to_vec(&attestation)?);
+    let verified = match key.algorithm {
+        state::KeyAlgorithm::Secp256k1 => deps.api.secp256k1_verify(&hash, &signature, &key.pubkey),
+        state::KeyAlgorithm::Ed25519 => deps.api.ed25519_verify(&hash, &signature, &key.pubkey),
+    }
+    .map_err(|_| ContractError::InvalidAttestation {})?;
+    if !verified {
+        return Err(ContractError::InvalidAttestation {});
+    }
+
+    state::save_attestation_nonce(deps.storage, &token_id, attestation.nonce)?;
+    store_kyc(
+        deps.storage,
+        &env,
+        &info.sender,
+        token_id,
+        "submitAttestation",
+        attestation.kyc,
+    )?;
+    state::remove_kyc_commitment(deps.storage, &token_id);
+
+    let resp = Response::new()
+        .add_attribute("action", "submitAttestation")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("keyId", attestation.key_id)
+        .add_attribute("nonce", attestation.nonce.to_string());
+
+    Ok(resp)
+}
+
+pub(crate) fn set_token_base_uri(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    uri: String,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+
+    apply_or_queue(
+        deps.storage,
+        &env,
+        &info.sender,
+        state::TimelockAction::SetTokenBaseUri { uri },
+    )
+}
+
+/// Sets the timelock delay. Once a delay is in place, changing it is itself
+/// timelocked.
+pub(crate) fn set_timelock_delay(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    seconds: u64,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+
+    apply_or_queue(
+        deps.storage,
+        &env,
+        &info.sender,
+        state::TimelockAction::SetTimelockDelay { seconds },
+    )
+}
+
+/// Executes a queued action once its ETA has passed, available to anyone.
+pub(crate) fn execute_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let queued = state::get_queued_action(deps.storage, id)?
+        .ok_or(ContractError::NonExistQueuedAction { id })?;
+    if env.block.time < queued.eta {
+        return Err(ContractError::TimelockNotReady {
+            id,
+            eta: queued.eta,
+        });
+    }
+    state::remove_queued_action(deps.storage, id);
+
+    let resp = apply_action(deps.storage, &env, &queued.proposed_by, queued.action)?
+        .add_attribute("queuedAction", id.to_string())
+        .add_attribute("executedBy", info.sender);
+    Ok(resp)
+}
+
+pub(crate) fn cancel_action(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+    if state::get_queued_action(deps.storage, id)?.is_none() {
+        return Err(ContractError::NonExistQueuedAction { id });
+    }
+    state::remove_queued_action(deps.storage, id);
+
+    let resp = Response::new()
+        .add_attribute("action", "cancelAction")
+        .add_attribute("id", id.to_string())
+        .add_attribute("sender", info.sender);
+    Ok(resp)
+}
+
+/// Applies `action` right away when no timelock delay is configured and
+/// queues it until `now + delay` otherwise. Callers authorize `proposed_by`.
+fn apply_or_queue(
+    storage: &mut dyn Storage,
+    env: &Env,
+    proposed_by: &Addr,
+    action: state::TimelockAction,
+) -> Result<Response, ContractError> {
+    let delay = state::get_timelock_delay(storage)?;
+    if delay == 0 {
+        return apply_action(storage, env, proposed_by, action);
+    }
+
+    let eta = env.block.time.plus_seconds(delay);
+    let id = state::queue_action(
+        storage,
+        &state::QueuedAction {
+            action,
+            proposed_by: proposed_by.clone(),
+            queued_at: env.block.time,
+            eta,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "queueAction")
+        .add_attribute("id", id.to_string())
+        .add_attribute("proposedBy", proposed_by)
+        .add_attribute("eta", eta.to_string());
+    Ok(resp)
+}
+
+fn apply_action(
+    storage: &mut dyn Storage,
+    env: &Env,
+    proposed_by: &Addr,
+    action: state::TimelockAction,
+) -> Result<Response, ContractError> {
+    if let Some(role) = action.proposer_role() {
+        if !state::has_role(storage, role, proposed_by)? {
+            let account = proposed_by.clone();
+            return Err(if role == state::DEFAULT_ADMIN_ROLE {
+                ContractError::InvalidAdminAccount { account }
+            } else {
+                ContractError::InvalidOperatorAccount { account }
+            });
+        }
+    }
+
+    let resp = match action {
+        state::TimelockAction::SetTokenBaseUri { uri } => {
+            state::set_token_base_uri(storage, uri.clone())?;
+            Response::new()
+                .add_attribute("action", "setTokenBaseUri")
+                .add_attribute("newUri", uri)
+        }
+        state::TimelockAction::GrantRole { role, user } => {
+            state::grant_role(storage, &role, user.clone())?;
+            Response::new()
+                .add_attribute("action", "grantRole")
+                .add_attribute("role", role)
+                .add_attribute("account", user)
+        }
+        state::TimelockAction::SetQuestions {
+            cooperator,
+            questions,
+        } => {
+            state::set_questions(storage, &cooperator, questions)?;
+            Response::new()
+                .add_attribute("action", "setQuestions")
+                .add_attribute("cooperator", cooperator)
+        }
+        state::TimelockAction::SetPolicy { cooperator, policy } => {
+            state::set_policy(storage, &cooperator, &policy)?;
+            Response::new()
+                .add_attribute("action", "setPolicy")
+                .add_attribute("cooperator", cooperator)
+        }
+        state::TimelockAction::TransferOwnership { new_owner, expiry } => {
+            let mut ownership = state::get_ownership(storage)?;
+            // a queued proposal lapses once its proposer no longer owns the contract
+            if ownership.owner.as_ref() != Some(proposed_by) {
+                return Err(ContractError::InvalidOwnerAccount {
+                    account: proposed_by.clone(),
+                });
+            }
+            if expiry.is_some_and(|expiry| expiry.is_expired(&env.block)) {
+                return Err(ContractError::OwnershipTransferExpired {});
+            }
+            ownership.pending_owner = Some(new_owner);
+            ownership.pending_expiry = expiry;
+            state::set_ownership(storage, &ownership)?;
+            ownership_response(proposed_by, ownership)
+        }
+        state::TimelockAction::SetTimelockDelay { seconds } => {
+            state::set_timelock_delay(storage, seconds)?;
+            Response::new()
+                .add_attribute("action", "setTimelockDelay")
+                .add_attribute("seconds", seconds.to_string())
+        }
+        state::TimelockAction::AddAttestorKey {
+            key_id,
+            algorithm,
+            pubkey,
+        } => {
+            // another proposal may have taken the id while this one waited
+            if state::get_attestor_key(storage, &key_id)?.is_some() {
+                return Err(ContractError::AlreadyExistAttestorKey { key_id });
+            }
+            state::save_attestor_key(
+                storage,
+                &key_id,
+                &state::AttestorKey {
+                    algorithm,
+                    pubkey,
+                    added_at: env.block.time,
+                    revoked_at: None,
+                },
+            )?;
+            Response::new()
+                .add_attribute("action", "addAttestorKey")
+                .add_attribute("keyId", key_id)
+        }
+        state::TimelockAction::RegisterIssuer {
+            issuer,
+            name,
+            trust_level,
+        } => {
+            state::save_issuer(
+                storage,
+                &issuer,
+                &state::IssuerInfo {
+                    name,
+                    trust_level,
+                    added_at: env.block.time,
+                },
+            )?;
+            Response::new()
+                .add_attribute("action", "registerIssuer")
+                .add_attribute("issuer", issuer)
+                .add_attribute("trustLevel", trust_level.to_string())
+        }
+        state::TimelockAction::RemoveIssuer { issuer } => {
+            state::remove_issuer(storage, &issuer);
+            Response::new()
+                .add_attribute("action", "removeIssuer")
+                .add_attribute("issuer", issuer)
+        }
+        state::TimelockAction::SetTiers { tiers } => {
+            state::set_tiers(storage, &tiers)?;
+            Response::new()
+                .add_attribute("action", "setTiers")
+                .add_attribute("tiers", tiers.join(","))
+        }
+        state::TimelockAction::SetGracePeriod { seconds } => {
+            state::set_grace_period(storage, seconds)?;
+            Response::new()
+                .add_attribute("action", "setGracePeriod")
+                .add_attribute("seconds", seconds.to_string())
+        }
+    };
+    Ok(resp)
+}
+
+pub(crate) fn approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cooperator: Addr,
+    token_id: TokenId,
+    cooperator_key: String,
+) -> Result<Response, ContractError> {
+    state::ensure_not_paused(deps.storage, |flags| flags.approvals, "approvals")?;
+    let owner = owner_or_delegate(deps.storage, &env, &info.sender, token_id, &cooperator)?;
+    ensure_not_denied(deps.as_ref(), &owner)?;
+    ensure_not_denied(deps.as_ref(), &cooperator)?;
+
+    approve_cooperator(
+        deps.storage,
+        &env,
+        &owner,
+        &cooperator,
+        token_id,
+        cooperator_key,
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "approve")
+        .add_attribute("cooperator", cooperator)
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("user", owner);
+
+    Ok(resp)
+}
+
+pub(crate) fn revoke(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cooperator: Addr,
+    token_id: TokenId,
+) -> Result<Response, ContractError> {
+    let owner = owner_or_delegate(deps.storage, &env, &info.sender, token_id, &cooperator)?;
+
+    if state::has_role(deps.storage, state::COOPERATOR_ROLE, &cooperator)? {
+        if state::has_approve(deps.storage, &cooperator, &owner) {
+            revoke_cooperator(deps.storage, &owner, &cooperator, token_id)?;
+        } else {
+            return Err(ContractError::InvalidRevokeFromCooperator { owner, cooperator });
+        }
+    } else {
+        return Err(ContractError::InvalidCooperatorAccount {
+            account: cooperator,
+        });
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "revoke")
+        .add_attribute("cooperator", cooperator)
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("user", owner);
+
+    Ok(resp)
+}
+
+pub(crate) fn approve_many(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    approvals: Vec<ApprovalRequest>,
+) -> Result<Response, ContractError> {
+    state::ensure_not_paused(deps.storage, |flags| flags.approvals, "approvals")?;
+    let limit = state::get_bulk_limit(deps.storage)?;
+    if approvals.len() > limit as usize {
+        return Err(ContractError::TooManyItems { limit });
+    }
+
+    let owner = info.sender;
+    let token_id = state::get_token(deps.storage, &owner)?.ok_or(ContractError::NoSBTExist {
+        user: owner.clone(),
+    })?;
+    ensure_not_denied(deps.as_ref(), &owner)?;
+
+    // Each item is validated before anything is written, so a failing item
+    // leaves no partial state behind and the rest of the batch still applies.
+    let mut events = vec![];
+    let mut approved = 0u32;
+    for ApprovalRequest {
+        cooperator,
+        cooperator_key,
+    } in approvals
+    {
+        let result = ensure_not_denied(deps.as_ref(), &cooperator).and_then(|()| {
+            approve_cooperator(
+                deps.storage,
+                &env,
+                &owner,
+                &cooperator,
+                token_id,
+                cooperator_key,
+            )
+        });
+        match result {
+            Ok(()) => {
+                approved += 1;
+                events.push(
+                    Event::new("approve")
+                        .add_attribute("cooperator", cooperator)
+                        .add_attribute("tokenId", token_id.to_string())
+                        .add_attribute("user", owner.clone()),
+                );
+            }
+            Err(err) => events.push(
+                Event::new("approve_failed")
+                    .add_attribute("cooperator", cooperator)
+                    .add_attribute("tokenId", token_id.to_string())
+                    .add_attribute("user", owner.clone())
+                    .add_attribute("reason", err.to_string()),
+            ),
+        }
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "approveMany")
+        .add_attribute("user", owner)
+        .add_attribute("approved", approved.to_string())
+        .add_attribute("failed", (events.len() as u32 - approved).to_string())
+        .add_events(events);
+
+    Ok(resp)
+}
+
+pub(crate) fn revoke_all(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let limit = state::get_bulk_limit(deps.storage)? as usize;
+    let owner = info.sender;
+
+    let mut approvals = state::list_approvals_by_user(deps.storage, &owner, None, limit + 1)?;
+    let remaining = approvals.len() > limit;
+    approvals.truncate(limit);
+
+    let mut events = vec![];
+    for (cooperator, approval) in approvals {
+        revoke_cooperator(deps.storage, &owner, &cooperator, approval.token_id)?;
+        events.push(
+            Event::new("revoke")
+                .add_attribute("cooperator", cooperator)
+                .add_attribute("tokenId", approval.token_id.to_string())
+                .add_attribute("user", owner.clone()),
+        );
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "revokeAll")
+        .add_attribute("user", owner)
+        .add_attribute("revoked", events.len().to_string())
+        .add_attribute("remaining", remaining.to_string())
+        .add_events(events);
+
+    Ok(resp)
+}
+
+/// Deletes KYC data, with every cooperator copy of it and the token's
+/// jurisdiction, and issuer attestations once the grace period and then the
+/// retention period have passed since their validity ended. Anyone may call
+/// this.
+pub(crate) fn prune_expired(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: u32,
+) -> Result<Response, ContractError> {
+    let limit = limit.min(state::get_bulk_limit(deps.storage)?) as usize;
+    let keep = state::get_grace_period(deps.storage)?
+        .saturating_add(state::get_retention_period(deps.storage)?);
+    let cutoff = Timestamp::from_nanos(
+        env.block
+            .time
+            .nanos()
+            .saturating_sub(keep.saturating_mul(1_000_000_000)),
+    );
+
+    let mut expired = state::list_expiring(deps.storage, cutoff, None, limit + 1)?;
+    let mut remaining = expired.len() > limit;
+    expired.truncate(limit);
+
+    let mut events = vec![];
+    for (token_id, validity) in expired {
+        state::remove_kyc(deps.storage, &token_id)?;
+        state::remove_kyc_commitment(deps.storage, &token_id);
+        state::remove_age_claims(deps.storage, &token_id);
+        state::remove_attribute_root(deps.storage, &token_id);
+        state::remove_token_jurisdiction(deps.storage, &token_id);
+        let cooperators = state::remove_approved_kyc_copies(deps.storage, token_id)?;
+        state::push_operator_action(
+            deps.storage,
+            &token_id,
+            "pruneExpired",
+            &info.sender,
+            env.block.time,
+        )?;
+        events.push(
+            Event::new("prune")
+                .add_attribute("tokenId", token_id.to_string())
+                .add_attribute("validity", validity.to_string())
+                .add_attribute("copies", cooperators.len().to_string()),
+        );
+    }
+
+    // issuer attestations carry their own payload and validity
+    let budget = limit - events.len();
+    let mut attestations = state::list_expired_attestations(deps.storage, cutoff, budget + 1)?;
+    remaining |= attestations.len() > budget;
+    attestations.truncate(budget);
+    for (token_id, issuer, validity) in attestations {
+        state::remove_issuer_attestation(deps.storage, token_id, &issuer)?;
+        events.push(
+            Event::new("prune")
+                .add_attribute("tokenId", token_id.to_string())
+                .add_attribute("validity", validity.to_string())
+                .add_attribute("issuer", issuer),
+        );
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "pruneExpired")
+        .add_attribute("pruned", events.len().to_string())
+        .add_attribute("remaining", remaining.to_string())
+        .add_events(events);
+
+    Ok(resp)
+}
+
+pub(crate) fn set_retention_period(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    seconds: u64,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+    state::set_retention_period(deps.storage, seconds)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "setRetentionPeriod")
+        .add_attribute("seconds", seconds.to_string());
+    Ok(resp)
+}
+
+/// Fills `index` in for up to `limit` more entries, within the bulk limit.
+/// Approvals found without a date are dated to the call.
+pub(crate) fn backfill(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    index: state::BackfillIndex,
+    limit: u32,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+    let limit = limit.min(state::get_bulk_limit(deps.storage)?) as usize;
+    let (name, (added, remaining)) = match index {
+        state::BackfillIndex::Approvals => (
+            "approvals",
+            state::backfill_approvals(deps.storage, env.block.time, limit)?,
+        ),
+        state::BackfillIndex::KycExpiry => (
+            "kycExpiry",
+            state::backfill_kyc_expiry(deps.storage, limit)?,
+        ),
+        state::BackfillIndex::KycCopies => (
+            "kycCopies",
+            state::backfill_kyc_copies(deps.storage, limit)?,
+        ),
+    };
+
+    let resp = Response::new()
+        .add_attribute("action", "backfill")
+        .add_attribute("index", name)
+        .add_attribute("added", added.to_string())
+        .add_attribute("remaining", remaining.to_string());
+    Ok(resp)
+}
+
+pub(crate) fn set_bulk_limit(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    limit: u32,
+) -> Result<Response, ContractError> {
+    if state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        state::set_bulk_limit(deps.storage, limit)?;
+    } else {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "setBulkLimit")
+        .add_attribute("limit", limit.to_string());
+    Ok(resp)
+}
+
+pub(crate) fn set_grace_period(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    seconds: u64,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+
+    apply_or_queue(
+        deps.storage,
+        &env,
+        &info.sender,
+        state::TimelockAction::SetGracePeriod { seconds },
+    )
+}
+
+pub(crate) fn update_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: msg::OwnershipAction,
+) -> Result<Response, ContractError> {
+    let mut ownership = state::get_ownership(deps.storage)?;
+    let sender = info.sender;
+
+    match action {
+        msg::OwnershipAction::TransferOwnership { new_owner, expiry } => {
+            if ownership.owner.as_ref() != Some(&sender) {
+                return Err(ContractError::InvalidOwnerAccount { account: sender });
+            }
+            if expiry.is_some_and(|expiry| expiry.is_expired(&env.block)) {
+                return Err(ContractError::OwnershipTransferExpired {});
+            }
+            // accepting grants the admin role, so proposals wait out the timelock
+            return apply_or_queue(
+                deps.storage,
+                &env,
+                &sender,
+                state::TimelockAction::TransferOwnership { new_owner, expiry },
+            );
+        }
+        msg::OwnershipAction::AcceptOwnership {} => {
+            if ownership.pending_owner.as_ref() != Some(&sender) {
+                return match ownership.pending_owner {
+                    Some(_) => Err(ContractError::InvalidPendingOwner { account: sender }),
+                    None => Err(ContractError::NonExistOwnershipTransfer {}),
+                };
+            }
+            if ownership
+                .pending_expiry
+                .is_some_and(|expiry| expiry.is_expired(&env.block))
+            {
+                return Err(ContractError::OwnershipTransferExpired {});
+            }
+
+            // the admin role follows the owner
+            if let Some(old_owner) = &ownership.owner {
+                state::revoke_role(deps.storage, state::DEFAULT_ADMIN_ROLE, old_owner)?;
+            }
+            if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &sender)? {
+                state::grant_role(deps.storage, state::DEFAULT_ADMIN_ROLE, sender.clone())?;
+            }
+            ownership = state::Ownership {
+                owner: Some(sender.clone()),
+                pending_owner: None,
+                pending_expiry: None,
+            };
+        }
+        msg::OwnershipAction::RenounceOwnership {} => {
+            if ownership.owner.as_ref() != Some(&sender) {
+                return Err(ContractError::InvalidOwnerAccount { account: sender });
+            }
+            state::revoke_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &sender)?;
+            ownership = state::Ownership::default();
+        }
+    }
+    state::set_ownership(deps.storage, &ownership)?;
+
+    Ok(ownership_response(&sender, ownership))
+}
+
+fn ownership_response(sender: &Addr, ownership: state::Ownership) -> Response {
+    // empty attribute values are rejected, so unset fields are omitted
+    let mut resp = Response::new()
+        .add_attribute("action", "updateOwnership")
+        .add_attribute("sender", sender);
+    if let Some(owner) = ownership.owner {
+        resp = resp.add_attribute("owner", owner);
+    }
+    if let Some(pending_owner) = ownership.pending_owner {
+        resp = resp.add_attribute("pendingOwner", pending_owner);
+    }
+    resp
+}
+
+/// Sets the emergency pause flags, available to admins and pausers.
+pub(crate) fn set_pause_flags(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    flags: state::PauseFlags,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::DEFAULT_ADMIN_ROLE, &info.sender)?
+        && !state::has_role(deps.storage, state::PAUSER_ROLE, &info.sender)?
+    {
+        return Err(ContractError::InvalidAdminAccount {
+            account: info.sender,
+        });
+    }
+    state::set_pause_flags(deps.storage, &flags)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "setPauseFlags")
+        .add_attribute("account", info.sender)
+        .add_attribute("attest", flags.attest.to_string())
+        .add_attribute("kycWrites", flags.kyc_writes.to_string())
+        .add_attribute("approvals", flags.approvals.to_string())
+        .add_attribute("verification", flags.verification.to_string());
+    Ok(resp)
+}
+
+pub(crate) fn grant_delegate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    delegate: Addr,
+    expires_at: Option<Timestamp>,
+    cooperators: Option<Vec<Addr>>,
+) -> Result<Response, ContractError> {
+    if !state::has_token(deps.storage, &info.sender) {
+        return Err(ContractError::NoSBTExist { user: info.sender });
+    }
+    if delegate == info.sender {
+        return Err(ContractError::InvalidDelegate { delegate });
+    }
+
+    state::save_delegate(
+        deps.storage,
+        &info.sender,
+        &delegate,
+        &state::DelegateGrant {
+            expires_at,
+            cooperators,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "grantDelegate")
+        .add_attribute("owner", info.sender)
+        .add_attribute("delegate", delegate);
+
+    Ok(resp)
+}
+
+pub(crate) fn revoke_delegate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    delegate: Addr,
+) -> Result<Response, ContractError> {
+    if state::get_delegate(deps.storage, &info.sender, &delegate)?.is_none() {
+        return Err(ContractError::NoDelegateExist {
+            owner: info.sender,
+            delegate,
+        });
+    }
+    state::remove_delegate(deps.storage, &info.sender, &delegate);
+
+    let resp = Response::new()
+        .add_attribute("action", "revokeDelegate")
+        .add_attribute("owner", info.sender)
+        .add_attribute("delegate", delegate);
+
+    Ok(resp)
+}
+
+pub(crate) fn freeze_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    reason: String,
+) -> Result<Response, ContractError> {
+    ensure_inspector_or_operator(deps.storage, &info.sender)?;
+    if !state::has_owner(deps.storage, &token_id)? {
+        return Err(ContractError::InvalidTokenId { token_id });
+    }
+    if state::is_frozen(deps.storage, &token_id) {
+        return Err(ContractError::FrozenToken { token_id });
+    }
+
+    state::push_operator_action(
+        deps.storage,
+        &token_id,
+        "freezeToken",
+        &info.sender,
+        env.block.time,
+    )?;
+    state::freeze_token(
+        deps.storage,
+        &token_id,
+        state::FreezeInfo {
+            reason: reason.clone(),
+            frozen_by: info.sender.clone(),
+            frozen_at: env.block.time,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "freezeToken")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("reason", reason)
+        .add_attribute("by", info.sender);
+
+    Ok(resp)
+}
+
+pub(crate) fn unfreeze_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    reason: String,
+) -> Result<Response, ContractError> {
+    ensure_inspector_or_operator(deps.storage, &info.sender)?;
+    if !state::is_frozen(deps.storage, &token_id) {
+        return Err(ContractError::NotFrozenToken { token_id });
+    }
+
+    state::push_operator_action(
+        deps.storage,
+        &token_id,
+        "unfreezeToken",
+        &info.sender,
+        env.block.time,
+    )?;
+    state::unfreeze_token(
+        deps.storage,
+        &token_id,
+        state::FreezeRecord {
+            action: state::FreezeAction::Unfreeze,
+            reason: reason.clone(),
+            by: info.sender.clone(),
+            at: env.block.time,
+        },
+    )?;
+
+    let resp = Response::new()
+        .add_attribute("action", "unfreezeToken")
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("reason", reason)
+        .add_attribute("by", info.sender);
+
+    Ok(resp)
+}
+
+/// Inspectors read the full audit view of a token through an execute call so
+/// that every inspection leaves an entry in the audit log. The view is
+/// returned as the response data.
+pub(crate) fn inspect_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: TokenId,
+    reason: String,
+) -> Result<Response, ContractError> {
+    if !state::has_role(deps.storage, state::INSPECTOR_ROLE, &info.sender)? {
+        return Err(ContractError::InvalidInspectorAccount {
+            account: info.sender,
+        });
+    }
+    let owner = state::get_owner(deps.storage, &token_id)?
+        .ok_or(ContractError::InvalidTokenId { token_id })?;
+
+    let audit_id = state::push_audit_entry(
+        deps.storage,
+        &state::AuditEntry {
+            inspector: info.sender.clone(),
+            token_id,
+            reason: reason.clone(),
+            at: env.block.time,
+        },
+    )?;
+
+    let approvals = query::user_approvals(deps.storage, &owner, None, usize::MAX)?;
+
+    let report = msg::InspectTokenResponse {
+        token_id,
+        owner,
+        freeze: state::get_freeze_info(deps.storage, &token_id)?,
+        approvals,
+        kyc_history: state::get_kyc_history(deps.storage, &token_id)?,
+        operator_actions: state::get_operator_actions(deps.storage, &token_id)?,
+    };
+
+    let resp = Response::new()
+        .add_attribute("action", "inspectToken")
+        .add_attribute("inspector", info.sender)
+        .add_attribute("tokenId", token_id.to_string())
+        .add_attribute("auditId", audit_id.to_string())
+        .add_attribute("reason", reason)
+        .set_data(to_binary(&report)?);
+
+    Ok(resp)
+}
+
+pub(crate) fn revoke_permit(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    permit_name: String,
+) -> Result<Response, ContractError> {
+    state::revoke_permit(deps.storage, &info.sender, &permit_name)?;
+
+    let resp = Response::new()
+        .add_attribute("action", "revokePermit")
+        .add_attribute("signer", info.sender)
+        .add_attribute("permitName", permit_name);
+
+    Ok(resp)
+}
+
+fn ensure_not_denied(deps: Deps, address: &Addr) -> Result<(), ContractError> {
+    match query::screen(deps, address)? {
+        Some(reason) => Err(ContractError::DeniedAddress {
+            address: address.clone(),
+            reason,
+        }),
+        None => Ok(()),
+    }
+}
+
+fn ensure_inspector_or_operator(
+    storage: &dyn Storage,
+    account: &Addr,
+) -> Result<(), ContractError> {
+    if state::has_role(storage, state::INSPECTOR_ROLE, account)?
+        || state::has_role(storage, state::OPERATOR_ROLE, account)?
+    {
+        Ok(())
+    } else {
+        Err(ContractError::InvalidInspectorAccount {
+            account: account.clone(),
+        })
+    }
+}
+
+/// Resolves the owner of `token_id`, accepting either the owner itself or one
+/// of its delegates whose grant covers `cooperator`.
+fn owner_or_delegate(
+    storage: &dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    token_id: TokenId,
+    cooperator: &Addr,
+) -> Result<Addr, ContractError> {
+    let owner =
+        state::get_owner(storage, &token_id)?.ok_or(ContractError::InvalidTokenId { token_id })?;
+    if owner != sender
+        && !state::is_delegate_for(storage, &owner, sender, cooperator, env.block.time)?
+    {
+        return Err(ContractError::InvalidOwner { token_id, owner });
+    }
+    Ok(owner)
+}
+
+fn approve_cooperator(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    cooperator: &Addr,
+    token_id: TokenId,
+    cooperator_key: String,
+) -> Result<(), ContractError> {
+    if !state::has_role(storage, state::COOPERATOR_ROLE, cooperator)? {
+        return Err(ContractError::InvalidCooperatorAccount {
+            account: cooperator.clone(),
+        });
+    }
+    if state::is_frozen(storage, &token_id) {
+        return Err(ContractError::FrozenToken { token_id });
+    }
+
+    state::save_approved_kyc_data(storage, cooperator, token_id, cooperator_key)?;
+    state::save_pu(storage, cooperator, owner, token_id)?;
+    state::save_approved(storage, cooperator, token_id)?;
+    state::save_approval(storage, cooperator, owner, token_id, env.block.time)?;
+    Ok(())
+}
+
+fn revoke_cooperator(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    cooperator: &Addr,
+    token_id: TokenId,
+) -> Result<(), ContractError> {
+    state::remove_pu(storage, cooperator, owner);
+    state::remove_approved(storage, cooperator, token_id)?;
+    state::remove_approval(storage, cooperator, owner)?;
+    state::remove_approved_kyc_data(storage, cooperator, token_id);
+    Ok(())
+}
@@ -1,9 +1,11 @@
 #![allow(dead_code)]
 mod contract;
+mod crypto;
 mod error;
 mod execute;
 pub mod msg;
 mod query;
 mod state;
+mod verifier;
 
 use error::ContractError;
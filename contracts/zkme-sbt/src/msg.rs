@@ -1,7 +1,13 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Timestamp};
+use cosmwasm_std::{Addr, Binary, Timestamp};
+use cw_utils::Expiration;
 
-use crate::state::{KycData, TokenId};
+use crate::state::{
+    AttestorKey, AuditEntry, BackfillIndex, DelegateGrant, DenyEntry, Erasure, FreezeInfo,
+    FreezeRecord, GuardianConfig, IssuerAttestation, IssuerInfo, KeyAlgorithm, KycCommitment,
+    KycData, KycMetadata, OperatorAction, Ownership, PauseFlags, PendingRecovery, QueuedAction,
+    Recovery, TokenId, VerifiedClaim, VerifyPolicy,
+};
 
 #[cw_serde]
 pub struct InitMsg {
@@ -21,12 +27,67 @@ pub enum ExecuteMsg {
         user: Addr,
     },
 
+    /// Two-step ownership transfer
+    UpdateOwnership(OwnershipAction),
+
+    /// Emergency pause, by admins or pausers.
+    SetPauseFlags {
+        flags: PauseFlags,
+    },
+
     /// Conf
     SetQuestions {
         cooperator: Addr,
         questions: Vec<String>,
     },
 
+    SetPolicy {
+        cooperator: Addr,
+        policy: VerifyPolicy,
+    },
+
+    SetTiers {
+        tiers: Vec<String>,
+    },
+
+    SetTier {
+        token_id: TokenId,
+        tier: String,
+    },
+
+    SetJurisdiction {
+        token_id: TokenId,
+        jurisdiction: String,
+    },
+
+    /// Age thresholds the holder of `token_id` is over, e.g. `[18, 21]`.
+    SetAgeClaims {
+        token_id: TokenId,
+        over: Vec<u8>,
+    },
+
+    /// Issuers
+    RegisterIssuer {
+        issuer: Addr,
+        name: String,
+        trust_level: u8,
+    },
+
+    RemoveIssuer {
+        issuer: Addr,
+    },
+
+    IssueAttestation {
+        token_id: TokenId,
+        validity: Timestamp,
+        data: String,
+        questions: Vec<String>,
+    },
+
+    WithdrawAttestation {
+        token_id: TokenId,
+    },
+
     /// zkMeSBT
     Attest {
         to: Addr,
@@ -37,6 +98,71 @@ pub enum ExecuteMsg {
         token_id: TokenId,
     },
 
+    /// Moves an SBT and its KYC data to a new wallet of the same holder.
+    RecoverToken {
+        token_id: TokenId,
+        new_owner: Addr,
+        move_approvals: bool,
+    },
+
+    /// Social recovery
+    SetGuardians {
+        guardians: Vec<Addr>,
+        threshold: u32,
+    },
+
+    ProposeRecovery {
+        token_id: TokenId,
+        new_owner: Addr,
+    },
+
+    ApproveRecovery {
+        token_id: TokenId,
+    },
+
+    CancelRecovery {
+        token_id: TokenId,
+    },
+
+    ExecuteRecovery {
+        token_id: TokenId,
+    },
+
+    SetRecoveryDelay {
+        seconds: u64,
+    },
+
+    SetGuardianLimit {
+        limit: u32,
+    },
+
+    /// Linked wallets
+    LinkAddress {
+        address: Addr,
+        pubkey: Binary,
+        signature: Binary,
+    },
+
+    UnlinkAddress {
+        address: Addr,
+    },
+
+    SetLinkLimit {
+        limit: u32,
+    },
+
+    /// Sei EVM
+    RegisterEvmAddress {
+        pubkey: Binary,
+        signature: Binary,
+    },
+
+    /// Wipes the sender's KYC data and all cooperator copies, burning the SBT
+    /// unless `keep_token` is set.
+    EraseMyData {
+        keep_token: bool,
+    },
+
     SetKycData {
         token_id: TokenId,
         key: String,
@@ -45,10 +171,79 @@ pub enum ExecuteMsg {
         questions: Vec<String>,
     },
 
+    SetKycCommitment {
+        token_id: TokenId,
+        key: String,
+        validity: Timestamp,
+        questions: Vec<String>,
+        commitment: KycCommitment,
+    },
+
+    /// Extends the validity of existing KYC data without rewriting it.
+    RenewKyc {
+        token_id: TokenId,
+        validity: Timestamp,
+    },
+
+    SetGracePeriod {
+        seconds: u64,
+    },
+
+    SetAttributeRoot {
+        token_id: TokenId,
+        root: Binary,
+    },
+
     SetTokenBaseUri {
         uri: String,
     },
 
+    /// Timelock
+    SetTimelockDelay {
+        seconds: u64,
+    },
+
+    ExecuteAction {
+        id: u64,
+    },
+
+    CancelAction {
+        id: u64,
+    },
+
+    /// Attestations
+    AddAttestorKey {
+        key_id: String,
+        algorithm: KeyAlgorithm,
+        pubkey: Binary,
+    },
+
+    RevokeAttestorKey {
+        key_id: String,
+    },
+
+    SubmitAttestation {
+        attestation: Attestation,
+        signature: Binary,
+    },
+
+    /// zkMeVerifier
+    /// Replacing the key of a question invalidates claims proven under the
+    /// previous one.
+    RegisterVerifyingKey {
+        question: String,
+        vk: Binary,
+    },
+
+    /// The circuit's leading public inputs are this contract's
+    /// `verifier::proof_domain` and `token_id`; `public_inputs` are the rest.
+    SubmitProof {
+        token_id: TokenId,
+        question: String,
+        proof: Binary,
+        public_inputs: Vec<Binary>,
+    },
+
     // TODO cw721 methods
     /// zkMeVerifyLite
     Approve {
@@ -61,6 +256,100 @@ pub enum ExecuteMsg {
         cooperator: Addr,
         token_id: TokenId,
     },
+
+    ApproveMany {
+        approvals: Vec<ApprovalRequest>,
+    },
+
+    RevokeAll {},
+
+    SetBulkLimit {
+        limit: u32,
+    },
+
+    /// Deletes up to `limit` KYC records and issuer attestations whose
+    /// validity ended more than the grace period plus the retention period
+    /// ago. KYC records go together with their cooperator copies and the
+    /// token's jurisdiction. Permissionless.
+    PruneExpired {
+        limit: u32,
+    },
+
+    SetRetentionPeriod {
+        seconds: u64,
+    },
+
+    /// Fills `index` in for up to `limit` more entries stored before it
+    /// existed, resuming where the previous call stopped. Admin only; repeat
+    /// until the `remaining` attribute is false.
+    Backfill {
+        index: BackfillIndex,
+        limit: u32,
+    },
+
+    GrantDelegate {
+        delegate: Addr,
+        expires_at: Option<Timestamp>,
+        cooperators: Option<Vec<Addr>>,
+    },
+
+    RevokeDelegate {
+        delegate: Addr,
+    },
+
+    /// Compliance
+    FreezeToken {
+        token_id: TokenId,
+        reason: String,
+    },
+
+    UnfreezeToken {
+        token_id: TokenId,
+        reason: String,
+    },
+
+    /// Returns an `InspectTokenResponse` as response data and records the
+    /// inspection in the audit log.
+    InspectToken {
+        token_id: TokenId,
+        reason: String,
+    },
+
+    AddToDenylist {
+        address: Addr,
+        reason: String,
+    },
+
+    RemoveFromDenylist {
+        address: Addr,
+    },
+
+    SetScreeningContract {
+        contract: Option<Addr>,
+    },
+
+    /// Query permits
+    RevokePermit {
+        permit_name: String,
+    },
+}
+
+#[cw_serde]
+pub enum OwnershipAction {
+    /// Proposes `new_owner`, who has to accept before `expiry`.
+    TransferOwnership {
+        new_owner: Addr,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    /// Gives up ownership and the owner's admin role for good.
+    RenounceOwnership {},
+}
+
+#[cw_serde]
+pub struct ApprovalRequest {
+    pub cooperator: Addr,
+    pub cooperator_key: String,
 }
 
 #[cw_serde]
@@ -70,13 +359,130 @@ pub enum QueryMsg {
     #[returns(IsRoleResponse)]
     IsRole { role: String, user: Addr },
 
+    #[returns(Ownership)]
+    Ownership {},
+
+    #[returns(PauseFlagsResponse)]
+    PauseFlags {},
+
     /// Conf
     #[returns(GetQuestionsResponse)]
     GetQuestions { cooperator: Addr },
 
+    #[returns(GetPolicyResponse)]
+    GetPolicy { cooperator: Addr },
+
+    #[returns(ListTiersResponse)]
+    ListTiers {},
+
+    #[returns(GetTierResponse)]
+    GetTier { user: Addr },
+
+    /// Issuers
+    #[returns(ListIssuersResponse)]
+    ListIssuers {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    /// Attestations carry issuer payloads, so reading them takes a permit of
+    /// the token owner or an inspector.
+    #[returns(ListAttestationsResponse)]
+    ListAttestations { token_id: TokenId, permit: Permit },
+
     /// zkMeSBT
     #[returns(GetKycDataResponse)]
-    GetKycData { token_id: TokenId },
+    GetKycData { token_id: TokenId, permit: Permit },
+
+    /// Tokens whose KYC data expires before `before`, soonest first.
+    /// `start_after` is the `(validity, token_id)` of the last token of the
+    /// previous page, so renewing or pruning it doesn't move the cursor.
+    #[returns(ListExpiringResponse)]
+    ListExpiring {
+        before: Timestamp,
+        start_after: Option<(Timestamp, TokenId)>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GracePeriodResponse)]
+    GracePeriod {},
+
+    #[returns(GetErasureResponse)]
+    GetErasure { user: Addr },
+
+    #[returns(GetRecoveryResponse)]
+    GetRecovery { account: Addr },
+
+    /// Sei EVM
+    #[returns(GetEvmMappingResponse)]
+    GetEvmMapping { evm_address: String },
+
+    /// The `sei1` and `0x` addresses derived from an uncompressed secp256k1 key.
+    #[returns(DeriveAddressesResponse)]
+    DeriveAddresses { pubkey: Binary },
+
+    #[returns(VerifyResponse)]
+    VerifyEvm {
+        cooperator: Addr,
+        evm_address: String,
+    },
+
+    #[returns(HasApprovedResponse)]
+    HasApprovedEvm {
+        cooperator: Addr,
+        evm_address: String,
+    },
+
+    #[returns(GetGuardiansResponse)]
+    GetGuardians { token_id: TokenId },
+
+    #[returns(ListLinkedAddressesResponse)]
+    ListLinkedAddresses { token_id: TokenId },
+
+    #[returns(LinkLimitResponse)]
+    LinkLimit {},
+
+    #[returns(RecoveryDelayResponse)]
+    RecoveryDelay {},
+
+    #[returns(GuardianLimitResponse)]
+    GuardianLimit {},
+
+    #[returns(RetentionPeriodResponse)]
+    RetentionPeriod {},
+
+    #[returns(GetKycCommitmentResponse)]
+    GetKycCommitment { token_id: TokenId },
+
+    #[returns(VerifyFieldResponse)]
+    VerifyField {
+        token_id: TokenId,
+        field: String,
+        value: String,
+        salt: Binary,
+    },
+
+    #[returns(VerifyAttributeProofResponse)]
+    VerifyAttributeProof {
+        token_id: TokenId,
+        leaf: String,
+        proof: Vec<Binary>,
+    },
+
+    #[returns(GetVerifyingKeyResponse)]
+    GetVerifyingKey { question: String },
+
+    #[returns(GetVerifiedClaimResponse)]
+    GetVerifiedClaim { token_id: TokenId, question: String },
+
+    #[returns(ListAttestorKeysResponse)]
+    ListAttestorKeys {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetAttestationNonceResponse)]
+    GetAttestationNonce { token_id: TokenId },
 
     #[returns(BalanceOfResponse)]
     BalanceOf { owner: Addr },
@@ -99,6 +505,9 @@ pub enum QueryMsg {
     #[returns(VerifyResponse)]
     Verify { cooperator: Addr, user: Addr },
 
+    #[returns(IsOverAgeResponse)]
+    IsOverAge { user: Addr, age: u8 },
+
     #[returns(HasApprovedResponse)]
     HasApproved { cooperator: Addr, user: Addr },
 
@@ -106,7 +515,11 @@ pub enum QueryMsg {
     GetUserTokenId { cooperator: Addr, user: Addr },
 
     #[returns(GetUserDataResponse)]
-    GetUserData { cooperator: Addr, user: Addr },
+    GetUserData {
+        cooperator: Addr,
+        user: Addr,
+        permit: Permit,
+    },
 
     #[returns(GetApprovedTokenIdResponse)]
     GetApprovedTokenId {
@@ -119,7 +532,130 @@ pub enum QueryMsg {
     GetApprovedLength { cooperator: Addr },
 
     #[returns(GetApprovedUserKycDataResponse)]
-    GetApprovedUserKycData { cooperator: Addr, user: Addr },
+    GetApprovedUserKycData {
+        cooperator: Addr,
+        user: Addr,
+        permit: Permit,
+    },
+
+    #[returns(BulkLimitResponse)]
+    BulkLimit {},
+
+    #[returns(ListApprovalsByUserResponse)]
+    ListApprovalsByUser {
+        user: Addr,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    #[returns(GetDelegateResponse)]
+    GetDelegate { owner: Addr, delegate: Addr },
+
+    #[returns(ListDelegatesResponse)]
+    ListDelegates {
+        owner: Addr,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    /// Compliance
+    #[returns(GetFreezeStatusResponse)]
+    GetFreezeStatus { token_id: TokenId },
+
+    #[returns(GetFreezeHistoryResponse)]
+    GetFreezeHistory { token_id: TokenId },
+
+    #[returns(GetScreeningResponse)]
+    GetScreening { address: Addr },
+
+    #[returns(ListDenylistResponse)]
+    ListDenylist {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+
+    #[returns(ListAuditLogResponse)]
+    ListAuditLog {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    /// Timelock
+    #[returns(PendingActionsResponse)]
+    PendingActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+/// KYC data signed off chain by an attestor key. `signature` in
+/// `SubmitAttestation` signs the SHA-256 hash of the JSON encoded attestation,
+/// and `nonce` must be higher than the last one accepted for the token.
+#[cw_serde]
+pub struct Attestation {
+    pub key_id: String,
+    pub chain_id: String,
+    pub contract: Addr,
+    pub token_id: TokenId,
+    pub nonce: u64,
+    pub kyc: KycData,
+}
+
+/// Proof that `signer` asked for a sensitive query. `signature` is a
+/// secp256k1 signature by `pubkey` over the ADR-036 sign doc of the JSON
+/// encoded `params`, as made by wallet `signArbitrary`, and `signer` must be
+/// the account of `pubkey`.
+#[cw_serde]
+pub struct Permit {
+    pub params: PermitParams,
+    pub signer: Addr,
+    pub pubkey: Binary,
+    pub signature: Binary,
+}
+
+#[cw_serde]
+pub struct PermitParams {
+    pub permit_name: String,
+    pub chain_id: String,
+    pub contract: Addr,
+    pub expires_at: Option<Timestamp>,
+}
+
+/// Proof of control signed by a wallet being linked to `token_id`: a
+/// secp256k1 signature over the ADR-036 sign doc of the JSON encoded params,
+/// with `address` as the signer.
+#[cw_serde]
+pub struct LinkParams {
+    pub chain_id: String,
+    pub contract: Addr,
+    pub token_id: TokenId,
+    pub address: Addr,
+}
+
+/// Signed by the uncompressed secp256k1 key of `address` to map it to its
+/// EVM address, in the same way as `LinkParams`.
+#[cw_serde]
+pub struct EvmLinkParams {
+    pub chain_id: String,
+    pub contract: Addr,
+    pub address: Addr,
+}
+
+/// Smart query interface an external screening contract must implement.
+#[cw_serde]
+pub enum ScreeningQueryMsg {
+    Screen { address: Addr },
+}
+
+#[cw_serde]
+pub struct ScreeningResponse {
+    pub denied: bool,
+    pub reason: Option<String>,
+}
+
+#[cw_serde]
+pub struct PauseFlagsResponse {
+    pub flags: PauseFlags,
 }
 
 #[cw_serde]
@@ -135,6 +671,50 @@ pub struct GetQuestionsResponse {
     pub questions: Vec<String>,
 }
 
+#[cw_serde]
+pub struct GetPolicyResponse {
+    pub cooperator: Addr,
+    pub policy: VerifyPolicy,
+}
+
+#[cw_serde]
+pub struct ListTiersResponse {
+    pub tiers: Vec<String>,
+}
+
+#[cw_serde]
+pub struct GetTierResponse {
+    pub user: Addr,
+    pub tier: Option<String>,
+    /// Position of `tier` in the registry, higher is stronger.
+    pub rank: Option<u32>,
+}
+
+#[cw_serde]
+pub struct IssuerInfoResponse {
+    pub issuer: Addr,
+    pub info: IssuerInfo,
+}
+
+#[cw_serde]
+pub struct ListIssuersResponse {
+    pub issuers: Vec<IssuerInfoResponse>,
+}
+
+#[cw_serde]
+pub struct IssuerAttestationInfo {
+    pub issuer: Addr,
+    /// `None` once the issuer has been removed.
+    pub trust_level: Option<u8>,
+    pub attestation: IssuerAttestation,
+}
+
+#[cw_serde]
+pub struct ListAttestationsResponse {
+    pub token_id: TokenId,
+    pub attestations: Vec<IssuerAttestationInfo>,
+}
+
 #[cw_serde]
 pub struct GetKycDataResponse {
     pub owner: Addr,
@@ -145,6 +725,169 @@ pub struct GetKycDataResponse {
     pub questions: Vec<String>,
 }
 
+#[cw_serde]
+pub struct ExpiringToken {
+    pub token_id: TokenId,
+    pub owner: Addr,
+    pub validity: Timestamp,
+}
+
+#[cw_serde]
+pub struct ListExpiringResponse {
+    pub tokens: Vec<ExpiringToken>,
+}
+
+#[cw_serde]
+pub struct GracePeriodResponse {
+    pub seconds: u64,
+}
+
+#[cw_serde]
+pub struct RetentionPeriodResponse {
+    pub seconds: u64,
+}
+
+#[cw_serde]
+pub struct GetRecoveryResponse {
+    pub account: Addr,
+    pub recovery: Option<Recovery>,
+}
+
+#[cw_serde]
+pub struct LinkedAddress {
+    pub address: Addr,
+    pub linked_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct ListLinkedAddressesResponse {
+    pub token_id: TokenId,
+    pub addresses: Vec<LinkedAddress>,
+}
+
+#[cw_serde]
+pub struct LinkLimitResponse {
+    pub limit: u32,
+}
+
+#[cw_serde]
+pub struct GetEvmMappingResponse {
+    pub evm_address: String,
+    pub address: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct DeriveAddressesResponse {
+    pub address: String,
+    pub evm_address: String,
+}
+
+#[cw_serde]
+pub struct GetScreeningResponse {
+    pub address: Addr,
+    pub denied_reason: Option<String>,
+}
+
+#[cw_serde]
+pub struct PendingAction {
+    pub id: u64,
+    pub queued: QueuedAction,
+}
+
+#[cw_serde]
+pub struct PendingActionsResponse {
+    pub delay: u64,
+    pub actions: Vec<PendingAction>,
+}
+
+#[cw_serde]
+pub struct DenylistEntry {
+    pub address: Addr,
+    pub entry: DenyEntry,
+}
+
+#[cw_serde]
+pub struct ListDenylistResponse {
+    pub entries: Vec<DenylistEntry>,
+    pub screening_contract: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct GetGuardiansResponse {
+    pub token_id: TokenId,
+    pub config: Option<GuardianConfig>,
+    pub pending: Option<PendingRecovery>,
+}
+
+#[cw_serde]
+pub struct RecoveryDelayResponse {
+    pub seconds: u64,
+}
+
+#[cw_serde]
+pub struct GuardianLimitResponse {
+    pub limit: u32,
+}
+
+#[cw_serde]
+pub struct GetErasureResponse {
+    pub user: Addr,
+    pub erasure: Option<Erasure>,
+}
+
+#[cw_serde]
+pub struct GetKycCommitmentResponse {
+    pub token_id: TokenId,
+    pub commitment: KycCommitment,
+}
+
+#[cw_serde]
+pub struct VerifyFieldResponse {
+    pub token_id: TokenId,
+    pub field: String,
+    pub result: bool,
+}
+
+#[cw_serde]
+pub struct VerifyAttributeProofResponse {
+    pub token_id: TokenId,
+    pub leaf: String,
+    pub included: bool,
+    /// Set when the holder failed screening.
+    pub denied_reason: Option<String>,
+}
+
+#[cw_serde]
+pub struct GetVerifyingKeyResponse {
+    pub question: String,
+    pub vk: Option<Binary>,
+    pub version: u32,
+}
+
+#[cw_serde]
+pub struct GetVerifiedClaimResponse {
+    pub token_id: TokenId,
+    pub question: String,
+    pub claim: Option<VerifiedClaim>,
+}
+
+#[cw_serde]
+pub struct AttestorKeyInfo {
+    pub key_id: String,
+    pub key: AttestorKey,
+}
+
+#[cw_serde]
+pub struct ListAttestorKeysResponse {
+    pub keys: Vec<AttestorKeyInfo>,
+}
+
+#[cw_serde]
+pub struct GetAttestationNonceResponse {
+    pub token_id: TokenId,
+    pub nonce: Option<u64>,
+}
+
 #[cw_serde]
 pub struct BalanceOfResponse {
     pub owner: Addr,
@@ -179,6 +922,23 @@ pub struct VerifyResponse {
     pub cooperator: Addr,
     pub user: Addr,
     pub result: bool,
+    pub frozen: bool,
+    /// Set when `result` only holds because the KYC data is within the grace
+    /// period after its validity.
+    pub in_grace: bool,
+    /// Set when the user erased their KYC data.
+    pub erased_at: Option<Timestamp>,
+    /// Set when the user is blocked by the denylist or screening contract.
+    pub denied_reason: Option<String>,
+}
+
+#[cw_serde]
+pub struct IsOverAgeResponse {
+    pub user: Addr,
+    pub age: u8,
+    pub result: bool,
+    /// Set when the user failed screening.
+    pub denied_reason: Option<String>,
 }
 
 #[cw_serde]
@@ -221,3 +981,76 @@ pub struct GetApprovedUserKycDataResponse {
     pub token_id: TokenId,
     pub data: KycData,
 }
+
+#[cw_serde]
+pub struct UserApproval {
+    pub cooperator: Addr,
+    pub token_id: TokenId,
+    pub approved_at: Timestamp,
+    pub expires_at: Option<Timestamp>,
+    pub scope: Vec<String>,
+}
+
+#[cw_serde]
+pub struct ListApprovalsByUserResponse {
+    pub user: Addr,
+    pub approvals: Vec<UserApproval>,
+}
+
+#[cw_serde]
+pub struct BulkLimitResponse {
+    pub limit: u32,
+}
+
+#[cw_serde]
+pub struct GetDelegateResponse {
+    pub owner: Addr,
+    pub delegate: Addr,
+    pub grant: Option<DelegateGrant>,
+}
+
+#[cw_serde]
+pub struct DelegateInfo {
+    pub delegate: Addr,
+    pub grant: DelegateGrant,
+}
+
+#[cw_serde]
+pub struct ListDelegatesResponse {
+    pub owner: Addr,
+    pub delegates: Vec<DelegateInfo>,
+}
+
+#[cw_serde]
+pub struct GetFreezeStatusResponse {
+    pub token_id: TokenId,
+    pub frozen: bool,
+    pub info: Option<FreezeInfo>,
+}
+
+#[cw_serde]
+pub struct GetFreezeHistoryResponse {
+    pub token_id: TokenId,
+    pub history: Vec<FreezeRecord>,
+}
+
+#[cw_serde]
+pub struct InspectTokenResponse {
+    pub token_id: TokenId,
+    pub owner: Addr,
+    pub freeze: Option<FreezeInfo>,
+    pub approvals: Vec<UserApproval>,
+    pub kyc_history: Vec<KycMetadata>,
+    pub operator_actions: Vec<OperatorAction>,
+}
+
+#[cw_serde]
+pub struct AuditLogEntry {
+    pub id: u64,
+    pub entry: AuditEntry,
+}
+
+#[cw_serde]
+pub struct ListAuditLogResponse {
+    pub entries: Vec<AuditLogEntry>,
+}
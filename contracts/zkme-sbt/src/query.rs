@@ -1,11 +1,15 @@
-use cosmwasm_std::{Addr, Deps, Env};
+use cosmwasm_std::{to_vec, Addr, Binary, Deps, Env, Storage, Timestamp};
 
 use crate::{
+    crypto,
     error::ContractError,
     msg,
     state::{self, TokenId},
 };
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 pub(crate) fn is_role(
     deps: Deps,
     _env: Env,
@@ -34,11 +38,14 @@ pub(crate) fn get_questions(
 
 pub(crate) fn get_kyc_data(
     deps: Deps,
-    _env: Env,
+    env: Env,
     token_id: TokenId,
+    permit: msg::Permit,
 ) -> Result<msg::GetKycDataResponse, ContractError> {
     let owner = state::get_owner(deps.storage, &token_id)?
         .ok_or(ContractError::InvalidTokenId { token_id })?;
+    let signer = verify_permit(deps, &env, &permit)?;
+    ensure_permitted(deps.storage, &signer, &[&owner])?;
     let kyc_data =
         state::get_kyc(deps.storage, &token_id)?.ok_or(ContractError::NonExistKyc { token_id })?;
 
@@ -52,6 +59,207 @@ pub(crate) fn get_kyc_data(
     })
 }
 
+pub(crate) fn get_kyc_commitment(
+    deps: Deps,
+    _env: Env,
+    token_id: TokenId,
+) -> Result<msg::GetKycCommitmentResponse, ContractError> {
+    let commitment = state::get_kyc_commitment(deps.storage, &token_id)?
+        .ok_or(ContractError::NonExistKyc { token_id })?;
+
+    Ok(msg::GetKycCommitmentResponse {
+        token_id,
+        commitment,
+    })
+}
+
+pub(crate) fn verify_field(
+    deps: Deps,
+    _env: Env,
+    token_id: TokenId,
+    field: String,
+    value: String,
+    salt: Binary,
+) -> Result<msg::VerifyFieldResponse, ContractError> {
+    let commitment = state::get_kyc_commitment(deps.storage, &token_id)?
+        .ok_or(ContractError::NonExistKyc { token_id })?;
+
+    let expected = crypto::field_commitment(&salt, &field, &value);
+    let result = commitment
+        .field_commitments
+        .iter()
+        .any(|committed| committed.field == field && committed.commitment.as_slice() == expected);
+
+    Ok(msg::VerifyFieldResponse {
+        token_id,
+        field,
+        result,
+    })
+}
+
+/// Checks a claim against the token's attribute root. Like `is_over_age`,
+/// a claim only holds while the holder passes screening, the token isn't
+/// frozen and its KYC is valid or in its grace period.
+pub(crate) fn verify_attribute_proof(
+    deps: Deps,
+    env: Env,
+    token_id: TokenId,
+    leaf: String,
+    proof: Vec<Binary>,
+) -> Result<msg::VerifyAttributeProofResponse, ContractError> {
+    state::ensure_not_paused(deps.storage, |flags| flags.verification, "verification")?;
+    let owner = state::get_owner(deps.storage, &token_id)?
+        .ok_or(ContractError::InvalidTokenId { token_id })?;
+    let root = state::get_attribute_root(deps.storage, &token_id)?
+        .ok_or(ContractError::NonExistKyc { token_id })?;
+    if let Some(reason) = screen(deps, &owner)? {
+        return Ok(msg::VerifyAttributeProofResponse {
+            token_id,
+            leaf,
+            included: false,
+            denied_reason: Some(reason),
+        });
+    }
+    let validity = state::get_kyc(deps.storage, &token_id)?.map(|kyc| kyc.validity);
+    let (valid, in_grace) = validity_status(deps.storage, &env, validity)?;
+    let included = !state::is_frozen(deps.storage, &token_id)
+        && (valid || in_grace)
+        && crypto::verify_merkle_proof(&root, &leaf, &proof);
+
+    Ok(msg::VerifyAttributeProofResponse {
+        token_id,
+        leaf,
+        included,
+        denied_reason: None,
+    })
+}
+
+pub(crate) fn get_verifying_key(
+    deps: Deps,
+    _env: Env,
+    question: String,
+) -> Result<msg::GetVerifyingKeyResponse, ContractError> {
+    let vk = state::get_verifying_key(deps.storage, &question)?;
+    let version = state::get_verifying_key_version(deps.storage, &question)?;
+    Ok(msg::GetVerifyingKeyResponse {
+        question,
+        vk,
+        version,
+    })
+}
+
+pub(crate) fn get_verified_claim(
+    deps: Deps,
+    _env: Env,
+    token_id: TokenId,
+    question: String,
+) -> Result<msg::GetVerifiedClaimResponse, ContractError> {
+    let claim = state::get_verified_claim(deps.storage, token_id, &question)?;
+    Ok(msg::GetVerifiedClaimResponse {
+        token_id,
+        question,
+        claim,
+    })
+}
+
+pub(crate) fn list_attestor_keys(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<msg::ListAttestorKeysResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let keys = state::list_attestor_keys(deps.storage, start_after.as_deref(), limit)?
+        .into_iter()
+        .map(|(key_id, key)| msg::AttestorKeyInfo { key_id, key })
+        .collect();
+
+    Ok(msg::ListAttestorKeysResponse { keys })
+}
+
+pub(crate) fn get_attestation_nonce(
+    deps: Deps,
+    _env: Env,
+    token_id: TokenId,
+) -> Result<msg::GetAttestationNonceResponse, ContractError> {
+    let nonce = state::get_attestation_nonce(deps.storage, &token_id)?;
+    Ok(msg::GetAttestationNonceResponse { token_id, nonce })
+}
+
+pub(crate) fn get_policy(
+    deps: Deps,
+    _env: Env,
+    cooperator: Addr,
+) -> Result<msg::GetPolicyResponse, ContractError> {
+    let policy = state::get_policy(deps.storage, &cooperator)?;
+    Ok(msg::GetPolicyResponse { cooperator, policy })
+}
+
+pub(crate) fn list_tiers(deps: Deps, _env: Env) -> Result<msg::ListTiersResponse, ContractError> {
+    let tiers = state::get_tiers(deps.storage)?;
+    Ok(msg::ListTiersResponse { tiers })
+}
+
+pub(crate) fn get_tier(
+    deps: Deps,
+    _env: Env,
+    user: Addr,
+) -> Result<msg::GetTierResponse, ContractError> {
+    let token_id = state::get_token(deps.storage, &user)?
+        .ok_or(ContractError::NoSBTExist { user: user.clone() })?;
+    let tier = state::get_token_tier(deps.storage, &token_id)?;
+    let rank = match &tier {
+        Some(tier) => state::tier_rank(deps.storage, tier)?.map(|rank| rank as u32),
+        None => None,
+    };
+
+    Ok(msg::GetTierResponse { user, tier, rank })
+}
+
+pub(crate) fn list_issuers(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> Result<msg::ListIssuersResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let issuers = state::list_issuers(deps.storage, start_after.as_ref(), limit)?
+        .into_iter()
+        .map(|(issuer, info)| msg::IssuerInfoResponse { issuer, info })
+        .collect();
+
+    Ok(msg::ListIssuersResponse { issuers })
+}
+
+pub(crate) fn list_attestations(
+    deps: Deps,
+    env: Env,
+    token_id: TokenId,
+    permit: msg::Permit,
+) -> Result<msg::ListAttestationsResponse, ContractError> {
+    let owner = state::get_owner(deps.storage, &token_id)?
+        .ok_or(ContractError::InvalidTokenId { token_id })?;
+    let signer = verify_permit(deps, &env, &permit)?;
+    ensure_permitted(deps.storage, &signer, &[&owner])?;
+    let attestations = state::list_issuer_attestations(deps.storage, token_id)?
+        .into_iter()
+        .map(|(issuer, attestation)| {
+            let trust_level =
+                state::get_issuer(deps.storage, &issuer)?.map(|info| info.trust_level);
+            Ok(msg::IssuerAttestationInfo {
+                issuer,
+                trust_level,
+                attestation,
+            })
+        })
+        .collect::<Result<Vec<_>, ContractError>>()?;
+
+    Ok(msg::ListAttestationsResponse {
+        token_id,
+        attestations,
+    })
+}
+
 pub(crate) fn balance_of(
     deps: Deps,
     _env: Env,
@@ -93,8 +301,11 @@ pub(crate) fn total_supply(
     _env: Env,
 ) -> Result<msg::TotalSupplyResponse, ContractError> {
     let counter = state::get_token_id(deps.storage)?;
+    let burned = state::get_burned_count(deps.storage)?;
 
-    Ok(msg::TotalSupplyResponse { total: counter - 1 })
+    Ok(msg::TotalSupplyResponse {
+        total: counter - 1 - burned,
+    })
 }
 
 pub(crate) fn token_uri(
@@ -116,27 +327,167 @@ pub(crate) fn verify(
     cooperator: Addr,
     user: Addr,
 ) -> Result<msg::VerifyResponse, ContractError> {
-    let token_id = state::get_token(deps.storage, &user)?
+    state::ensure_not_paused(deps.storage, |flags| flags.verification, "verification")?;
+    let holder = state::resolve_holder(deps.storage, &user)?;
+    if let Some(erasure) = state::get_erasure(deps.storage, &holder)? {
+        return Ok(msg::VerifyResponse {
+            cooperator,
+            user,
+            result: false,
+            frozen: state::is_frozen(deps.storage, &erasure.token_id),
+            in_grace: false,
+            erased_at: Some(erasure.erased_at),
+            denied_reason: None,
+        });
+    }
+    if let Some(reason) = screen_holder(deps, &user, &holder)? {
+        return Ok(msg::VerifyResponse {
+            cooperator,
+            user,
+            result: false,
+            frozen: false,
+            in_grace: false,
+            erased_at: None,
+            denied_reason: Some(reason),
+        });
+    }
+    let token_id = state::get_token(deps.storage, &holder)?
         .ok_or(ContractError::NoSBTExist { user: user.clone() })?;
-    let user_data =
-        state::get_kyc(deps.storage, &token_id)?.ok_or(ContractError::NonExistKyc { token_id })?;
+    let policy = state::get_policy(deps.storage, &cooperator)?;
+    let records = kyc_records(deps.storage, token_id, &policy.issuer_rule)?;
     let cooperator_data = state::get_questions(deps.storage, &cooperator)?;
+    let frozen = state::is_frozen(deps.storage, &token_id);
+
+    let tier_satisfied = match &policy.min_tier {
+        Some(min_tier) => {
+            let min_rank = state::tier_rank(deps.storage, min_tier)?;
+            let rank = match state::get_token_tier(deps.storage, &token_id)? {
+                Some(tier) => state::tier_rank(deps.storage, &tier)?,
+                None => None,
+            };
+            matches!((rank, min_rank), (Some(rank), Some(min_rank)) if rank >= min_rank)
+        }
+        None => true,
+    };
 
-    let result: bool = user_data.validity >= env.block.time
-        && cooperator_data
-            .and_then(|questions| {
-                Some(
-                    questions
-                        .iter()
-                        .all(|question| user_data.questions.contains(question)),
-                )
+    // Latest validity among the records answering every question.
+    let validity = cooperator_data.and_then(|questions| {
+        records
+            .iter()
+            .filter(|(_, user_questions)| {
+                questions.iter().all(|question| {
+                    user_questions.contains(question)
+                        || state::has_verified_claim(deps.storage, token_id, question)
+                })
             })
-            .unwrap_or(false);
+            .map(|(validity, _)| *validity)
+            .max()
+    });
+    let (valid, in_grace) = validity_status(deps.storage, &env, validity)?;
+
+    let jurisdiction_satisfied = match &policy.jurisdictions {
+        Some(rule) => {
+            rule.allows(state::get_token_jurisdiction(deps.storage, &token_id)?.as_deref())
+        }
+        None => true,
+    };
+
+    let age_satisfied = match policy.min_age {
+        Some(min_age) => state::is_over_age(deps.storage, &token_id, min_age)?,
+        None => true,
+    };
+
+    let result: bool =
+        !frozen && tier_satisfied && jurisdiction_satisfied && age_satisfied && (valid || in_grace);
 
     Ok(msg::VerifyResponse {
         cooperator,
         user,
         result,
+        frozen,
+        in_grace: result && in_grace,
+        erased_at: None,
+        denied_reason: None,
+    })
+}
+
+/// Whether `validity` is still current, or expired but within the grace period.
+fn validity_status(
+    storage: &dyn Storage,
+    env: &Env,
+    validity: Option<Timestamp>,
+) -> Result<(bool, bool), ContractError> {
+    let grace_period = state::get_grace_period(storage)?;
+    let valid = validity.is_some_and(|validity| validity >= env.block.time);
+    let in_grace = !valid
+        && validity.is_some_and(|validity| validity.plus_seconds(grace_period) >= env.block.time);
+    Ok((valid, in_grace))
+}
+
+/// The `(validity, questions)` records of a token accepted by `rule`.
+fn kyc_records(
+    storage: &dyn Storage,
+    token_id: TokenId,
+    rule: &state::IssuerRule,
+) -> Result<Vec<(Timestamp, Vec<String>)>, ContractError> {
+    let zkme = state::get_kyc(storage, &token_id)?.map(|kyc| (kyc.validity, kyc.questions));
+
+    match rule {
+        state::IssuerRule::Zkme => Ok(vec![zkme.ok_or(ContractError::NonExistKyc { token_id })?]),
+        state::IssuerRule::AnyTrusted { min_trust_level } => {
+            let mut records: Vec<_> = zkme.into_iter().collect();
+            for (issuer, attestation) in state::list_issuer_attestations(storage, token_id)? {
+                let trusted = state::get_issuer(storage, &issuer)?
+                    .is_some_and(|info| info.trust_level >= *min_trust_level);
+                if trusted {
+                    records.push((attestation.validity, attestation.questions));
+                }
+            }
+            Ok(records)
+        }
+        state::IssuerRule::Specific { issuer } => {
+            if state::get_issuer(storage, issuer)?.is_none() {
+                return Ok(vec![]);
+            }
+            Ok(state::get_issuer_attestation(storage, token_id, issuer)?
+                .map(|attestation| (attestation.validity, attestation.questions))
+                .into_iter()
+                .collect())
+        }
+    }
+}
+
+/// Age claims only count while the zkMe KYC record backing them is current,
+/// or within its grace period, and the holder passes screening.
+pub(crate) fn is_over_age(
+    deps: Deps,
+    env: Env,
+    user: Addr,
+    age: u8,
+) -> Result<msg::IsOverAgeResponse, ContractError> {
+    state::ensure_not_paused(deps.storage, |flags| flags.verification, "verification")?;
+    let holder = state::resolve_holder(deps.storage, &user)?;
+    if let Some(reason) = screen_holder(deps, &user, &holder)? {
+        return Ok(msg::IsOverAgeResponse {
+            user,
+            age,
+            result: false,
+            denied_reason: Some(reason),
+        });
+    }
+    let token_id = state::get_token(deps.storage, &holder)?
+        .ok_or(ContractError::NoSBTExist { user: user.clone() })?;
+    let validity = state::get_kyc(deps.storage, &token_id)?.map(|kyc| kyc.validity);
+    let (valid, in_grace) = validity_status(deps.storage, &env, validity)?;
+    let result = !state::is_frozen(deps.storage, &token_id)
+        && (valid || in_grace)
+        && state::is_over_age(deps.storage, &token_id, age)?;
+
+    Ok(msg::IsOverAgeResponse {
+        user,
+        age,
+        result,
+        denied_reason: None,
     })
 }
 
@@ -146,7 +497,8 @@ pub(crate) fn has_approved(
     cooperator: Addr,
     user: Addr,
 ) -> Result<msg::HasApprovedResponse, ContractError> {
-    let is_approved = state::has_approve(deps.storage, &cooperator, &user);
+    let holder = state::resolve_holder(deps.storage, &user)?;
+    let is_approved = state::has_approve(deps.storage, &cooperator, &holder);
     Ok(msg::HasApprovedResponse {
         cooperator,
         user,
@@ -174,10 +526,14 @@ pub(crate) fn get_user_token_id(
 
 pub(crate) fn get_user_data(
     deps: Deps,
-    _env: Env,
+    env: Env,
     cooperator: Addr,
     user: Addr,
+    permit: msg::Permit,
 ) -> Result<msg::GetUserDataResponse, ContractError> {
+    let signer = verify_permit(deps, &env, &permit)?;
+    ensure_permitted(deps.storage, &signer, &[&cooperator, &user])?;
+    ensure_not_erased(deps.storage, &user)?;
     if !state::has_approve(deps.storage, &cooperator, &user) {
         return Err(ContractError::NoApprovementExist {
             cooperator,
@@ -192,6 +548,94 @@ pub(crate) fn get_user_data(
     Ok(msg::GetUserDataResponse { user, kyc_data })
 }
 
+/// Why `address` is blocked, checking the local denylist and then the
+/// screening contract, if one is configured.
+pub(crate) fn screen(deps: Deps, address: &Addr) -> Result<Option<String>, ContractError> {
+    if let Some(entry) = state::get_deny_entry(deps.storage, address)? {
+        return Ok(Some(entry.reason));
+    }
+    if let Some(contract) = state::get_screening_contract(deps.storage)? {
+        let resp: msg::ScreeningResponse = deps.querier.query_wasm_smart(
+            contract,
+            &msg::ScreeningQueryMsg::Screen {
+                address: address.clone(),
+            },
+        )?;
+        if resp.denied {
+            return Ok(Some(resp.reason.unwrap_or_default()));
+        }
+    }
+    Ok(None)
+}
+
+/// Screens `user` and, for a linked wallet, its primary holder as well, so a
+/// denied linked wallet fails even when the holder is clean.
+fn screen_holder(deps: Deps, user: &Addr, holder: &Addr) -> Result<Option<String>, ContractError> {
+    match screen(deps, user)? {
+        Some(reason) => Ok(Some(reason)),
+        None if holder != user => screen(deps, holder),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn get_screening(
+    deps: Deps,
+    _env: Env,
+    address: Addr,
+) -> Result<msg::GetScreeningResponse, ContractError> {
+    let denied_reason = screen(deps, &address)?;
+    Ok(msg::GetScreeningResponse {
+        address,
+        denied_reason,
+    })
+}
+
+pub(crate) fn list_denylist(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> Result<msg::ListDenylistResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let entries = state::list_denylist(deps.storage, start_after.as_ref(), limit)?
+        .into_iter()
+        .map(|(address, entry)| msg::DenylistEntry { address, entry })
+        .collect();
+    let screening_contract = state::get_screening_contract(deps.storage)?;
+    Ok(msg::ListDenylistResponse {
+        entries,
+        screening_contract,
+    })
+}
+
+pub(crate) fn pending_actions(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<msg::PendingActionsResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let actions = state::list_queued_actions(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(id, queued)| msg::PendingAction { id, queued })
+        .collect();
+    Ok(msg::PendingActionsResponse {
+        delay: state::get_timelock_delay(deps.storage)?,
+        actions,
+    })
+}
+
+/// Fails with `ErasedData` once `user` has erased their KYC data.
+fn ensure_not_erased(storage: &dyn Storage, user: &Addr) -> Result<(), ContractError> {
+    match state::get_erasure(storage, user)? {
+        Some(erasure) => Err(ContractError::ErasedData {
+            user: user.clone(),
+            erased_at: erasure.erased_at,
+        }),
+        None => Ok(()),
+    }
+}
+
 pub(crate) fn get_approved_token_id(
     deps: Deps,
     _env: Env,
@@ -239,10 +683,20 @@ pub(crate) fn get_approved_length(
 
 pub(crate) fn get_approved_user_kyc_data(
     deps: Deps,
-    _env: Env,
+    env: Env,
     cooperator: Addr,
     user: Addr,
+    permit: msg::Permit,
 ) -> Result<msg::GetApprovedUserKycDataResponse, ContractError> {
+    let signer = verify_permit(deps, &env, &permit)?;
+    ensure_permitted(deps.storage, &signer, &[&cooperator, &user])?;
+    ensure_not_erased(deps.storage, &user)?;
+    if !state::has_approve(deps.storage, &cooperator, &user) {
+        return Err(ContractError::NoApprovementExist {
+            cooperator,
+            user: user.clone(),
+        });
+    }
     let token_id = state::get_token(deps.storage, &user)?
         .ok_or(ContractError::NoSBTExist { user: user.clone() })?;
 
@@ -260,3 +714,349 @@ pub(crate) fn get_approved_user_kyc_data(
         data,
     })
 }
+
+pub(crate) fn list_approvals_by_user(
+    deps: Deps,
+    _env: Env,
+    user: Addr,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> Result<msg::ListApprovalsByUserResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let approvals = user_approvals(deps.storage, &user, start_after.as_ref(), limit)?;
+
+    Ok(msg::ListApprovalsByUserResponse { user, approvals })
+}
+
+/// Approvals of `user` joined with the expiry and scope of the KYC copy each
+/// cooperator received.
+pub(crate) fn user_approvals(
+    storage: &dyn Storage,
+    user: &Addr,
+    start_after: Option<&Addr>,
+    limit: usize,
+) -> Result<Vec<msg::UserApproval>, ContractError> {
+    state::list_approvals_by_user(storage, user, start_after, limit)?
+        .into_iter()
+        .map(|(cooperator, approval)| {
+            let kyc = state::get_approved_kyc_data(storage, &cooperator, approval.token_id)?;
+            Ok(msg::UserApproval {
+                token_id: approval.token_id,
+                approved_at: approval.approved_at,
+                expires_at: kyc.as_ref().map(|kyc| kyc.validity),
+                scope: kyc.map(|kyc| kyc.questions).unwrap_or_default(),
+                cooperator,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn list_expiring(
+    deps: Deps,
+    _env: Env,
+    before: Timestamp,
+    start_after: Option<(Timestamp, TokenId)>,
+    limit: Option<u32>,
+) -> Result<msg::ListExpiringResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let tokens = state::list_expiring(deps.storage, before, start_after, limit)?
+        .into_iter()
+        .map(|(token_id, validity)| {
+            let owner = state::get_owner(deps.storage, &token_id)?
+                .ok_or(ContractError::InvalidTokenId { token_id })?;
+            Ok(msg::ExpiringToken {
+                token_id,
+                owner,
+                validity,
+            })
+        })
+        .collect::<Result<_, ContractError>>()?;
+
+    Ok(msg::ListExpiringResponse { tokens })
+}
+
+pub(crate) fn grace_period(
+    deps: Deps,
+    _env: Env,
+) -> Result<msg::GracePeriodResponse, ContractError> {
+    let seconds = state::get_grace_period(deps.storage)?;
+    Ok(msg::GracePeriodResponse { seconds })
+}
+
+pub(crate) fn get_erasure(
+    deps: Deps,
+    _env: Env,
+    user: Addr,
+) -> Result<msg::GetErasureResponse, ContractError> {
+    let erasure = state::get_erasure(deps.storage, &user)?;
+    Ok(msg::GetErasureResponse { user, erasure })
+}
+
+pub(crate) fn get_recovery(
+    deps: Deps,
+    _env: Env,
+    account: Addr,
+) -> Result<msg::GetRecoveryResponse, ContractError> {
+    let recovery = state::get_recovery(deps.storage, &account)?;
+    Ok(msg::GetRecoveryResponse { account, recovery })
+}
+
+pub(crate) fn get_guardians(
+    deps: Deps,
+    _env: Env,
+    token_id: TokenId,
+) -> Result<msg::GetGuardiansResponse, ContractError> {
+    let config = state::get_guardians(deps.storage, &token_id)?;
+    let pending = state::get_pending_recovery(deps.storage, &token_id)?;
+    Ok(msg::GetGuardiansResponse {
+        token_id,
+        config,
+        pending,
+    })
+}
+
+pub(crate) fn recovery_delay(
+    deps: Deps,
+    _env: Env,
+) -> Result<msg::RecoveryDelayResponse, ContractError> {
+    let seconds = state::get_recovery_delay(deps.storage)?;
+    Ok(msg::RecoveryDelayResponse { seconds })
+}
+
+pub(crate) fn guardian_limit(
+    deps: Deps,
+    _env: Env,
+) -> Result<msg::GuardianLimitResponse, ContractError> {
+    let limit = state::get_guardian_limit(deps.storage)?;
+    Ok(msg::GuardianLimitResponse { limit })
+}
+
+pub(crate) fn list_linked_addresses(
+    deps: Deps,
+    _env: Env,
+    token_id: TokenId,
+) -> Result<msg::ListLinkedAddressesResponse, ContractError> {
+    let addresses = state::list_links(deps.storage, token_id)?
+        .into_iter()
+        .map(|(address, linked_at)| msg::LinkedAddress { address, linked_at })
+        .collect();
+    Ok(msg::ListLinkedAddressesResponse {
+        token_id,
+        addresses,
+    })
+}
+
+pub(crate) fn link_limit(deps: Deps, _env: Env) -> Result<msg::LinkLimitResponse, ContractError> {
+    let limit = state::get_link_limit(deps.storage)?;
+    Ok(msg::LinkLimitResponse { limit })
+}
+
+pub(crate) fn get_evm_mapping(
+    deps: Deps,
+    _env: Env,
+    evm_address: String,
+) -> Result<msg::GetEvmMappingResponse, ContractError> {
+    let evm_address = normalize_evm_address(&evm_address)?;
+    let address = state::get_evm_account(deps.storage, &evm_address)?;
+    Ok(msg::GetEvmMappingResponse {
+        evm_address,
+        address,
+    })
+}
+
+pub(crate) fn derive_addresses(
+    _deps: Deps,
+    _env: Env,
+    pubkey: Binary,
+) -> Result<msg::DeriveAddressesResponse, ContractError> {
+    let address = crypto::compress_pubkey(&pubkey)
+        .and_then(|compressed| crypto::sei_address(&compressed))
+        .ok_or(ContractError::InvalidEvmPubkey {})?;
+    let evm_address = crypto::evm_address(&pubkey)
+        .map(|address| crypto::format_evm_address(&address))
+        .ok_or(ContractError::InvalidEvmPubkey {})?;
+    Ok(msg::DeriveAddressesResponse {
+        address,
+        evm_address,
+    })
+}
+
+pub(crate) fn verify_evm(
+    deps: Deps,
+    env: Env,
+    cooperator: Addr,
+    evm_address: String,
+) -> Result<msg::VerifyResponse, ContractError> {
+    let user = evm_account(deps.storage, &evm_address)?;
+    verify(deps, env, cooperator, user)
+}
+
+pub(crate) fn has_approved_evm(
+    deps: Deps,
+    env: Env,
+    cooperator: Addr,
+    evm_address: String,
+) -> Result<msg::HasApprovedResponse, ContractError> {
+    let user = evm_account(deps.storage, &evm_address)?;
+    has_approved(deps, env, cooperator, user)
+}
+
+fn normalize_evm_address(evm_address: &str) -> Result<String, ContractError> {
+    crypto::parse_evm_address(evm_address)
+        .map(|address| crypto::format_evm_address(&address))
+        .ok_or(ContractError::InvalidEvmAddress {
+            address: evm_address.to_string(),
+        })
+}
+
+/// The Sei account registered for `evm_address`.
+fn evm_account(storage: &dyn Storage, evm_address: &str) -> Result<Addr, ContractError> {
+    let evm_address = normalize_evm_address(evm_address)?;
+    state::get_evm_account(storage, &evm_address)?.ok_or(ContractError::NonExistEvmMapping {
+        address: evm_address,
+    })
+}
+
+pub(crate) fn retention_period(
+    deps: Deps,
+    _env: Env,
+) -> Result<msg::RetentionPeriodResponse, ContractError> {
+    let seconds = state::get_retention_period(deps.storage)?;
+    Ok(msg::RetentionPeriodResponse { seconds })
+}
+
+pub(crate) fn ownership(deps: Deps, _env: Env) -> Result<state::Ownership, ContractError> {
+    state::get_ownership(deps.storage)
+}
+
+pub(crate) fn pause_flags(deps: Deps, _env: Env) -> Result<msg::PauseFlagsResponse, ContractError> {
+    let flags = state::get_pause_flags(deps.storage)?;
+    Ok(msg::PauseFlagsResponse { flags })
+}
+
+pub(crate) fn bulk_limit(deps: Deps, _env: Env) -> Result<msg::BulkLimitResponse, ContractError> {
+    let limit = state::get_bulk_limit(deps.storage)?;
+    Ok(msg::BulkLimitResponse { limit })
+}
+
+pub(crate) fn get_delegate(
+    deps: Deps,
+    _env: Env,
+    owner: Addr,
+    delegate: Addr,
+) -> Result<msg::GetDelegateResponse, ContractError> {
+    let grant = state::get_delegate(deps.storage, &owner, &delegate)?;
+    Ok(msg::GetDelegateResponse {
+        owner,
+        delegate,
+        grant,
+    })
+}
+
+pub(crate) fn list_delegates(
+    deps: Deps,
+    _env: Env,
+    owner: Addr,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> Result<msg::ListDelegatesResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let delegates = state::list_delegates(deps.storage, &owner, start_after.as_ref(), limit)?
+        .into_iter()
+        .map(|(delegate, grant)| msg::DelegateInfo { delegate, grant })
+        .collect();
+
+    Ok(msg::ListDelegatesResponse { owner, delegates })
+}
+
+pub(crate) fn get_freeze_status(
+    deps: Deps,
+    _env: Env,
+    token_id: TokenId,
+) -> Result<msg::GetFreezeStatusResponse, ContractError> {
+    let info = state::get_freeze_info(deps.storage, &token_id)?;
+    Ok(msg::GetFreezeStatusResponse {
+        token_id,
+        frozen: info.is_some(),
+        info,
+    })
+}
+
+pub(crate) fn get_freeze_history(
+    deps: Deps,
+    _env: Env,
+    token_id: TokenId,
+) -> Result<msg::GetFreezeHistoryResponse, ContractError> {
+    let history = state::get_freeze_history(deps.storage, &token_id)?;
+    Ok(msg::GetFreezeHistoryResponse { token_id, history })
+}
+
+pub(crate) fn list_audit_log(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<msg::ListAuditLogResponse, ContractError> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let entries = state::list_audit_log(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(id, entry)| msg::AuditLogEntry { id, entry })
+        .collect();
+
+    Ok(msg::ListAuditLogResponse { entries })
+}
+
+/// Checks that `permit` was signed for this contract on this chain and is
+/// still valid, returning the signer.
+fn verify_permit(deps: Deps, env: &Env, permit: &msg::Permit) -> Result<Addr, ContractError> {
+    let msg::Permit {
+        params,
+        signer,
+        pubkey,
+        signature,
+    } = permit;
+
+    if params.chain_id != env.block.chain_id || params.contract != env.contract.address {
+        return Err(ContractError::InvalidPermit {});
+    }
+    if params
+        .expires_at
+        .is_some_and(|expires_at| env.block.time >= expires_at)
+    {
+        return Err(ContractError::PermitExpired {});
+    }
+    if state::is_permit_revoked(deps.storage, signer, &params.permit_name) {
+        return Err(ContractError::PermitRevoked {
+            permit_name: params.permit_name.clone(),
+        });
+    }
+    if !crypto::is_pubkey_account(signer, pubkey) {
+        return Err(ContractError::InvalidPermit {});
+    }
+
+    let hash = crypto::sha256(&crypto::adr036_sign_bytes(signer, &to_vec(params)?));
+    let verified = deps
+        .api
+        .secp256k1_verify(&hash, signature, pubkey)
+        .map_err(|_| ContractError::InvalidPermit {})?;
+    if !verified {
+        return Err(ContractError::InvalidPermit {});
+    }
+
+    Ok(signer.clone())
+}
+
+/// Sensitive data is readable by the listed parties and by inspectors.
+fn ensure_permitted(
+    storage: &dyn Storage,
+    signer: &Addr,
+    parties: &[&Addr],
+) -> Result<(), ContractError> {
+    if parties.contains(&signer) || state::has_role(storage, state::INSPECTOR_ROLE, signer)? {
+        Ok(())
+    } else {
+        Err(ContractError::UnauthorizedPermit {
+            signer: signer.clone(),
+        })
+    }
+}
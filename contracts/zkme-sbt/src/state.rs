@@ -2,15 +2,17 @@ use std::ops::Not;
 
 use crate::ContractError;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Storage, Timestamp};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Binary, Order, StdResult, Storage, Timestamp};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, KeyDeserialize, Map, MultiIndex};
+use cw_utils::Expiration;
 
 pub type TokenId = u64;
 
-pub const DEFAULT_ADMIN_ROLE: &'static str = "default_admin_role";
-pub const OPERATOR_ROLE: &'static str = "zkme_operator";
-pub const COOPERATOR_ROLE: &'static str = "zkme_cooperator";
-pub const INSPECTOR_ROLE: &'static str = "zkme_inspector";
+pub const DEFAULT_ADMIN_ROLE: &str = "default_admin_role";
+pub const OPERATOR_ROLE: &str = "zkme_operator";
+pub const COOPERATOR_ROLE: &str = "zkme_cooperator";
+pub const INSPECTOR_ROLE: &str = "zkme_inspector";
+pub const PAUSER_ROLE: &str = "zkme_pauser";
 
 #[cw_serde]
 pub struct RoleData {
@@ -70,17 +72,13 @@ pub fn revoke_role(
     role: &str,
     account: &Addr,
 ) -> Result<(), ContractError> {
-    if has_role(storage, role, &account)? {
+    if has_role(storage, role, account)? {
         ROLES.update(
             storage,
             role,
             move |role_data| -> Result<_, ContractError> {
                 if let Some(mut data) = role_data {
-                    data.members = data
-                        .members
-                        .into_iter()
-                        .filter(|addr| addr != &account)
-                        .collect();
+                    data.members.retain(|addr| addr != account);
                     Ok(data)
                 } else {
                     Err(ContractError::UpdatingErrorSlot)
@@ -91,132 +89,1510 @@ pub fn revoke_role(
     Ok(())
 }
 
+/// Single contract owner, discoverable by tooling, transferred in two steps.
+/// The owner always holds `DEFAULT_ADMIN_ROLE`.
+#[cw_serde]
+#[derive(Default)]
+pub struct Ownership {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
+}
+
+pub const OWNERSHIP: Item<Ownership> = Item::new("ownership");
+
+pub fn get_ownership(storage: &dyn Storage) -> Result<Ownership, ContractError> {
+    Ok(OWNERSHIP.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn has_ownership(storage: &dyn Storage) -> bool {
+    OWNERSHIP.exists(storage)
+}
+
+pub fn set_ownership(
+    storage: &mut dyn Storage,
+    ownership: &Ownership,
+) -> Result<(), ContractError> {
+    Ok(OWNERSHIP.save(storage, ownership)?)
+}
+
+/// The first member of `DEFAULT_ADMIN_ROLE`, used to seed ownership of
+/// contracts instantiated before it existed.
+pub fn first_admin(storage: &dyn Storage) -> Result<Option<Addr>, ContractError> {
+    Ok(ROLES
+        .may_load(storage, DEFAULT_ADMIN_ROLE)?
+        .and_then(|roles| roles.members.first().cloned()))
+}
+
+/// Emergency switches, each halting one group of operations.
+#[cw_serde]
+#[derive(Default)]
+pub struct PauseFlags {
+    pub attest: bool,
+    pub kyc_writes: bool,
+    pub approvals: bool,
+    pub verification: bool,
+}
+
+pub const PAUSE_FLAGS: Item<PauseFlags> = Item::new("pause_flags");
+
+pub fn get_pause_flags(storage: &dyn Storage) -> Result<PauseFlags, ContractError> {
+    Ok(PAUSE_FLAGS.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn set_pause_flags(storage: &mut dyn Storage, flags: &PauseFlags) -> Result<(), ContractError> {
+    Ok(PAUSE_FLAGS.save(storage, flags)?)
+}
+
+/// Fails with `Paused` when the flag selected by `paused` is set.
+pub fn ensure_not_paused(
+    storage: &dyn Storage,
+    paused: fn(&PauseFlags) -> bool,
+    operation: &str,
+) -> Result<(), ContractError> {
+    if paused(&get_pause_flags(storage)?) {
+        return Err(ContractError::Paused {
+            operation: operation.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Admin actions that change the trust model and therefore go through the
+/// timelock when a delay is configured.
+#[cw_serde]
+pub enum TimelockAction {
+    SetTokenBaseUri {
+        uri: String,
+    },
+    GrantRole {
+        role: String,
+        user: Addr,
+    },
+    SetQuestions {
+        cooperator: Addr,
+        questions: Vec<String>,
+    },
+    SetPolicy {
+        cooperator: Addr,
+        policy: VerifyPolicy,
+    },
+    TransferOwnership {
+        new_owner: Addr,
+        expiry: Option<Expiration>,
+    },
+    SetTimelockDelay {
+        seconds: u64,
+    },
+    AddAttestorKey {
+        key_id: String,
+        algorithm: KeyAlgorithm,
+        pubkey: Binary,
+    },
+    RegisterIssuer {
+        issuer: Addr,
+        name: String,
+        trust_level: u8,
+    },
+    RemoveIssuer {
+        issuer: Addr,
+    },
+    SetTiers {
+        tiers: Vec<String>,
+    },
+    SetGracePeriod {
+        seconds: u64,
+    },
+}
+
+impl TimelockAction {
+    /// Role the proposer must still hold when the action is applied, so a
+    /// queued action lapses once its proposer loses it. Ownership transfers
+    /// check the owner instead.
+    pub fn proposer_role(&self) -> Option<&'static str> {
+        match self {
+            TimelockAction::GrantRole { .. }
+            | TimelockAction::SetQuestions { .. }
+            | TimelockAction::SetPolicy { .. } => Some(OPERATOR_ROLE),
+            TimelockAction::SetTokenBaseUri { .. }
+            | TimelockAction::SetTimelockDelay { .. }
+            | TimelockAction::AddAttestorKey { .. }
+            | TimelockAction::RegisterIssuer { .. }
+            | TimelockAction::RemoveIssuer { .. }
+            | TimelockAction::SetTiers { .. }
+            | TimelockAction::SetGracePeriod { .. } => Some(DEFAULT_ADMIN_ROLE),
+            TimelockAction::TransferOwnership { .. } => None,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct QueuedAction {
+    pub action: TimelockAction,
+    pub proposed_by: Addr,
+    pub queued_at: Timestamp,
+    pub eta: Timestamp,
+}
+
+/// Seconds a queued action waits before it can be executed. Zero applies
+/// sensitive actions immediately.
+pub const TIMELOCK_DELAY: Item<u64> = Item::new("timelock_delay");
+
+pub fn get_timelock_delay(storage: &dyn Storage) -> Result<u64, ContractError> {
+    Ok(TIMELOCK_DELAY.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn set_timelock_delay(storage: &mut dyn Storage, seconds: u64) -> Result<(), ContractError> {
+    Ok(TIMELOCK_DELAY.save(storage, &seconds)?)
+}
+
+pub const ACTION_COUNTER: Item<u64> = Item::new("action_counter");
+
+pub const PENDING_ACTIONS: Map<u64, QueuedAction> = Map::new("pending_actions");
+
+pub fn queue_action(
+    storage: &mut dyn Storage,
+    action: &QueuedAction,
+) -> Result<u64, ContractError> {
+    let id = ACTION_COUNTER.may_load(storage)?.unwrap_or_default() + 1;
+    ACTION_COUNTER.save(storage, &id)?;
+    PENDING_ACTIONS.save(storage, id, action)?;
+    Ok(id)
+}
+
+pub fn get_queued_action(
+    storage: &dyn Storage,
+    id: u64,
+) -> Result<Option<QueuedAction>, ContractError> {
+    Ok(PENDING_ACTIONS.may_load(storage, id)?)
+}
+
+pub fn remove_queued_action(storage: &mut dyn Storage, id: u64) {
+    PENDING_ACTIONS.remove(storage, id)
+}
+
+pub fn list_queued_actions(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: usize,
+) -> Result<Vec<(u64, QueuedAction)>, ContractError> {
+    let start = start_after.map(Bound::exclusive);
+    let actions = PENDING_ACTIONS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(actions)
+}
+
 pub const CONF_QUESTIONS: Map<&Addr, Vec<String>> = Map::new("conf_questions");
 
-pub fn has_questions(storage: &dyn Storage, cooperator: &Addr) -> bool {
-    CONF_QUESTIONS.has(storage, cooperator)
+pub fn has_questions(storage: &dyn Storage, cooperator: &Addr) -> bool {
+    CONF_QUESTIONS.has(storage, cooperator)
+}
+
+pub fn set_questions(
+    storage: &mut dyn Storage,
+    cooperator: &Addr,
+    questions: Vec<String>,
+) -> Result<(), ContractError> {
+    if has_questions(storage, cooperator) {
+        let _ = CONF_QUESTIONS.update(
+            storage,
+            cooperator,
+            move |_old_questions| -> Result<_, ContractError> { Ok(questions) },
+        );
+        Ok(())
+    } else {
+        Ok(CONF_QUESTIONS.save(storage, cooperator, &questions)?)
+    }
+}
+
+pub fn get_questions(
+    storage: &dyn Storage,
+    cooperator: &Addr,
+) -> Result<Option<Vec<String>>, ContractError> {
+    if has_questions(storage, cooperator) {
+        Ok(Some(CONF_QUESTIONS.load(storage, cooperator)?))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum IssuerRule {
+    /// KYC data written by zkMe operators, the original behavior.
+    #[default]
+    Zkme,
+    /// zkMe KYC data or an attestation by any registered issuer with at least this trust level.
+    AnyTrusted { min_trust_level: u8 },
+    /// Only an attestation by this registered issuer.
+    Specific { issuer: Addr },
+}
+
+/// Allow or deny list of ISO 3166 jurisdictions. An entry for a country,
+/// e.g. `US`, also covers its subdivisions such as `US-CA`.
+#[cw_serde]
+pub enum JurisdictionRule {
+    Allow { jurisdictions: Vec<String> },
+    Deny { jurisdictions: Vec<String> },
+}
+
+impl JurisdictionRule {
+    pub fn codes(&self) -> &[String] {
+        match self {
+            JurisdictionRule::Allow { jurisdictions }
+            | JurisdictionRule::Deny { jurisdictions } => jurisdictions,
+        }
+    }
+
+    /// Tokens without a jurisdiction never satisfy a rule.
+    pub fn allows(&self, jurisdiction: Option<&str>) -> bool {
+        let Some(jurisdiction) = jurisdiction else {
+            return false;
+        };
+        let listed = self.codes().iter().any(|code| {
+            jurisdiction == code
+                || jurisdiction
+                    .strip_prefix(code.as_str())
+                    .is_some_and(|rest| rest.starts_with('-'))
+        });
+        match self {
+            JurisdictionRule::Allow { .. } => listed,
+            JurisdictionRule::Deny { .. } => !listed,
+        }
+    }
+}
+
+/// Whether `code` is an ISO 3166-1 alpha-2 code, optionally followed by an
+/// ISO 3166-2 subdivision, e.g. `DE` or `US-CA`.
+// `Option::is_none_or` needs a newer toolchain than CosmWasm builds pin
+#[allow(clippy::unnecessary_map_or)]
+pub fn is_jurisdiction_code(code: &str) -> bool {
+    let (country, subdivision) = match code.split_once('-') {
+        Some((country, subdivision)) => (country, Some(subdivision)),
+        None => (code, None),
+    };
+    country.len() == 2
+        && country.bytes().all(|b| b.is_ascii_uppercase())
+        && subdivision.map_or(true, |subdivision| {
+            (1..=3).contains(&subdivision.len())
+                && subdivision
+                    .bytes()
+                    .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        })
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct VerifyPolicy {
+    pub issuer_rule: IssuerRule,
+    pub min_tier: Option<String>,
+    pub jurisdictions: Option<JurisdictionRule>,
+    pub min_age: Option<u8>,
+}
+
+pub const CONF_POLICIES: Map<&Addr, VerifyPolicy> = Map::new("conf_policies");
+
+pub fn set_policy(
+    storage: &mut dyn Storage,
+    cooperator: &Addr,
+    policy: &VerifyPolicy,
+) -> Result<(), ContractError> {
+    Ok(CONF_POLICIES.save(storage, cooperator, policy)?)
+}
+
+pub fn get_policy(storage: &dyn Storage, cooperator: &Addr) -> Result<VerifyPolicy, ContractError> {
+    Ok(CONF_POLICIES
+        .may_load(storage, cooperator)?
+        .unwrap_or_default())
+}
+
+/// KYC tiers ordered from lowest to highest.
+pub const TIERS: Item<Vec<String>> = Item::new("tiers");
+
+pub fn get_tiers(storage: &dyn Storage) -> Result<Vec<String>, ContractError> {
+    Ok(TIERS.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn set_tiers(storage: &mut dyn Storage, tiers: &Vec<String>) -> Result<(), ContractError> {
+    Ok(TIERS.save(storage, tiers)?)
+}
+
+pub fn tier_rank(storage: &dyn Storage, tier: &str) -> Result<Option<usize>, ContractError> {
+    Ok(get_tiers(storage)?.iter().position(|t| t == tier))
+}
+
+pub const TOKEN_TIERS: Map<&TokenId, String> = Map::new("token_tiers");
+
+pub fn save_token_tier(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    tier: &String,
+) -> Result<(), ContractError> {
+    Ok(TOKEN_TIERS.save(storage, token_id, tier)?)
+}
+
+pub fn remove_token_tier(storage: &mut dyn Storage, token_id: &TokenId) {
+    TOKEN_TIERS.remove(storage, token_id);
+}
+
+pub fn get_token_tier(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+) -> Result<Option<String>, ContractError> {
+    Ok(TOKEN_TIERS.may_load(storage, token_id)?)
+}
+
+/// Operator-attested ISO 3166 jurisdiction of a token holder, evaluated
+/// against policies. No smart query returns it, but it is stored in plaintext
+/// and readable through raw storage queries and the `SetJurisdiction` message.
+pub const TOKEN_JURISDICTIONS: Map<&TokenId, String> = Map::new("token_jurisdictions");
+
+pub fn save_token_jurisdiction(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    jurisdiction: &String,
+) -> Result<(), ContractError> {
+    Ok(TOKEN_JURISDICTIONS.save(storage, token_id, jurisdiction)?)
+}
+
+pub fn get_token_jurisdiction(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+) -> Result<Option<String>, ContractError> {
+    Ok(TOKEN_JURISDICTIONS.may_load(storage, token_id)?)
+}
+
+pub fn remove_token_jurisdiction(storage: &mut dyn Storage, token_id: &TokenId) {
+    TOKEN_JURISDICTIONS.remove(storage, token_id)
+}
+
+/// Age thresholds a token holder is known to be over, derived off chain from
+/// the birthdate by operators. Being over 21 implies being over 18.
+pub const AGE_CLAIMS: Map<&TokenId, Vec<u8>> = Map::new("age_claims");
+
+pub fn save_age_claims(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    thresholds: &Vec<u8>,
+) -> Result<(), ContractError> {
+    Ok(AGE_CLAIMS.save(storage, token_id, thresholds)?)
+}
+
+pub fn remove_age_claims(storage: &mut dyn Storage, token_id: &TokenId) {
+    AGE_CLAIMS.remove(storage, token_id)
+}
+
+pub fn is_over_age(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+    age: u8,
+) -> Result<bool, ContractError> {
+    Ok(AGE_CLAIMS
+        .may_load(storage, token_id)?
+        .is_some_and(|thresholds| thresholds.iter().any(|threshold| *threshold >= age)))
+}
+
+pub const COUNTER: Item<TokenId> = Item::new("token_id");
+
+pub fn initialize_counter(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    Ok(COUNTER.save(storage, &1)?)
+}
+
+pub fn increase_counter(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let _ = COUNTER.update(storage, |counter| -> Result<_, ContractError> {
+        Ok(counter + 1)
+    })?;
+    Ok(())
+}
+
+pub fn get_token_id(storage: &dyn Storage) -> Result<TokenId, ContractError> {
+    Ok(COUNTER.load(storage)?)
+}
+
+/// Number of tokens removed through erasure, subtracted from the total supply.
+pub const BURNED_COUNT: Item<u64> = Item::new("burned_count");
+
+pub fn get_burned_count(storage: &dyn Storage) -> Result<u64, ContractError> {
+    Ok(BURNED_COUNT.may_load(storage)?.unwrap_or_default())
+}
+
+/// Removes the SBT of `owner` without touching the token id counter.
+pub fn burn_token(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    token_id: &TokenId,
+) -> Result<(), ContractError> {
+    OWNER_MAP.remove(storage, token_id);
+    TOKEN_MAP.remove(storage, owner);
+    TOKEN_TIERS.remove(storage, token_id);
+    let burned = get_burned_count(storage)?;
+    Ok(BURNED_COUNT.save(storage, &(burned + 1))?)
+}
+
+pub const OWNER_MAP: Map<&TokenId, Addr> = Map::new("owner_map");
+
+pub fn has_owner(storage: &dyn Storage, k: &TokenId) -> Result<bool, ContractError> {
+    Ok(OWNER_MAP.has(storage, k))
+}
+
+pub fn save_owner(storage: &mut dyn Storage, k: &TokenId, v: &Addr) -> Result<(), ContractError> {
+    if has_owner(storage, k)?.not() {
+        OWNER_MAP.save(storage, k, v)?;
+    }
+    Ok(())
+}
+
+pub fn get_owner(storage: &dyn Storage, k: &TokenId) -> Result<Option<Addr>, ContractError> {
+    Ok(OWNER_MAP.may_load(storage, k)?)
+}
+
+pub const TOKEN_MAP: Map<&Addr, TokenId> = Map::new("token_map");
+
+pub fn has_token(storage: &dyn Storage, k: &Addr) -> bool {
+    TOKEN_MAP.has(storage, k)
+}
+
+pub fn save_token(storage: &mut dyn Storage, k: &Addr, v: &TokenId) -> Result<(), ContractError> {
+    Ok(TOKEN_MAP.save(storage, k, v)?)
+}
+
+pub fn get_token(storage: &dyn Storage, k: &Addr) -> Result<Option<TokenId>, ContractError> {
+    Ok(TOKEN_MAP.may_load(storage, k)?)
+}
+
+/// Moves the SBT `token_id` from `from` to `to`.
+pub fn transfer_token(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    from: &Addr,
+    to: &Addr,
+) -> Result<(), ContractError> {
+    OWNER_MAP.save(storage, token_id, to)?;
+    TOKEN_MAP.remove(storage, from);
+    Ok(TOKEN_MAP.save(storage, to, token_id)?)
+}
+
+#[cw_serde]
+pub struct Recovery {
+    pub token_id: TokenId,
+    pub new_owner: Addr,
+    pub recovered_by: Addr,
+    pub recovered_at: Timestamp,
+}
+
+/// Former holders whose SBT was recovered to another address. They can't be
+/// attested again.
+pub const RECOVERED_ACCOUNTS: Map<&Addr, Recovery> = Map::new("recovered_accounts");
+
+pub fn save_recovery(
+    storage: &mut dyn Storage,
+    account: &Addr,
+    recovery: &Recovery,
+) -> Result<(), ContractError> {
+    Ok(RECOVERED_ACCOUNTS.save(storage, account, recovery)?)
+}
+
+pub fn get_recovery(
+    storage: &dyn Storage,
+    account: &Addr,
+) -> Result<Option<Recovery>, ContractError> {
+    Ok(RECOVERED_ACCOUNTS.may_load(storage, account)?)
+}
+
+pub fn is_recovered(storage: &dyn Storage, account: &Addr) -> bool {
+    RECOVERED_ACCOUNTS.has(storage, account)
+}
+
+#[cw_serde]
+pub struct GuardianConfig {
+    pub guardians: Vec<Addr>,
+    pub threshold: u32,
+}
+
+/// Guardians nominated by a token holder for social recovery.
+pub const GUARDIANS: Map<&TokenId, GuardianConfig> = Map::new("guardians");
+
+pub fn save_guardians(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    config: &GuardianConfig,
+) -> Result<(), ContractError> {
+    Ok(GUARDIANS.save(storage, token_id, config)?)
+}
+
+pub fn remove_guardians(storage: &mut dyn Storage, token_id: &TokenId) {
+    GUARDIANS.remove(storage, token_id)
+}
+
+pub fn get_guardians(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+) -> Result<Option<GuardianConfig>, ContractError> {
+    Ok(GUARDIANS.may_load(storage, token_id)?)
+}
+
+#[cw_serde]
+pub struct PendingRecovery {
+    pub new_owner: Addr,
+    pub proposed_by: Addr,
+    pub proposed_at: Timestamp,
+    pub approvals: Vec<Addr>,
+    /// When the guardian threshold was reached, starting the recovery delay.
+    pub approved_at: Option<Timestamp>,
+}
+
+pub const PENDING_RECOVERIES: Map<&TokenId, PendingRecovery> = Map::new("pending_recoveries");
+
+pub fn save_pending_recovery(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    recovery: &PendingRecovery,
+) -> Result<(), ContractError> {
+    Ok(PENDING_RECOVERIES.save(storage, token_id, recovery)?)
+}
+
+pub fn remove_pending_recovery(storage: &mut dyn Storage, token_id: &TokenId) {
+    PENDING_RECOVERIES.remove(storage, token_id)
+}
+
+pub fn get_pending_recovery(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+) -> Result<Option<PendingRecovery>, ContractError> {
+    Ok(PENDING_RECOVERIES.may_load(storage, token_id)?)
+}
+
+pub const DEFAULT_RECOVERY_DELAY: u64 = 3 * 24 * 60 * 60;
+
+/// Seconds between guardians reaching their threshold and the recovery
+/// becoming executable.
+pub const RECOVERY_DELAY: Item<u64> = Item::new("recovery_delay");
+
+pub fn get_recovery_delay(storage: &dyn Storage) -> Result<u64, ContractError> {
+    Ok(RECOVERY_DELAY
+        .may_load(storage)?
+        .unwrap_or(DEFAULT_RECOVERY_DELAY))
+}
+
+pub fn set_recovery_delay(storage: &mut dyn Storage, seconds: u64) -> Result<(), ContractError> {
+    Ok(RECOVERY_DELAY.save(storage, &seconds)?)
+}
+
+pub const DEFAULT_GUARDIAN_LIMIT: u32 = 10;
+
+/// Largest guardian set a token may have, kept apart from the bulk limit so
+/// tuning batch sizes doesn't lock owners out of their guardian sets.
+pub const GUARDIAN_LIMIT: Item<u32> = Item::new("guardian_limit");
+
+pub fn get_guardian_limit(storage: &dyn Storage) -> Result<u32, ContractError> {
+    Ok(GUARDIAN_LIMIT
+        .may_load(storage)?
+        .unwrap_or(DEFAULT_GUARDIAN_LIMIT))
+}
+
+pub fn set_guardian_limit(storage: &mut dyn Storage, limit: u32) -> Result<(), ContractError> {
+    Ok(GUARDIAN_LIMIT.save(storage, &limit)?)
+}
+
+/// Additional wallets linked to a token, keyed by the linked address.
+pub const LINKED_ADDRESSES: Map<&Addr, TokenId> = Map::new("linked_addresses");
+
+/// Reverse lookup of `LINKED_ADDRESSES` with the time each link was made.
+pub const TOKEN_LINKS: Map<(TokenId, &Addr), Timestamp> = Map::new("token_links");
+
+pub fn save_link(
+    storage: &mut dyn Storage,
+    token_id: TokenId,
+    address: &Addr,
+    linked_at: Timestamp,
+) -> Result<(), ContractError> {
+    LINKED_ADDRESSES.save(storage, address, &token_id)?;
+    Ok(TOKEN_LINKS.save(storage, (token_id, address), &linked_at)?)
+}
+
+pub fn remove_link(storage: &mut dyn Storage, token_id: TokenId, address: &Addr) {
+    LINKED_ADDRESSES.remove(storage, address);
+    TOKEN_LINKS.remove(storage, (token_id, address));
+}
+
+pub fn get_linked_token(
+    storage: &dyn Storage,
+    address: &Addr,
+) -> Result<Option<TokenId>, ContractError> {
+    Ok(LINKED_ADDRESSES.may_load(storage, address)?)
+}
+
+pub fn is_linked(storage: &dyn Storage, address: &Addr) -> bool {
+    LINKED_ADDRESSES.has(storage, address)
+}
+
+pub fn list_links(
+    storage: &dyn Storage,
+    token_id: TokenId,
+) -> Result<Vec<(Addr, Timestamp)>, ContractError> {
+    let links = TOKEN_LINKS
+        .prefix(token_id)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(links)
+}
+
+/// The primary holder behind `user`: the owner of the token `user` is linked
+/// to, or `user` itself.
+pub fn resolve_holder(storage: &dyn Storage, user: &Addr) -> Result<Addr, ContractError> {
+    match get_linked_token(storage, user)? {
+        Some(token_id) => Ok(get_owner(storage, &token_id)?.unwrap_or_else(|| user.clone())),
+        None => Ok(user.clone()),
+    }
+}
+
+pub const DEFAULT_LINK_LIMIT: u32 = 5;
+
+pub const LINK_LIMIT: Item<u32> = Item::new("link_limit");
+
+pub fn get_link_limit(storage: &dyn Storage) -> Result<u32, ContractError> {
+    Ok(LINK_LIMIT.may_load(storage)?.unwrap_or(DEFAULT_LINK_LIMIT))
+}
+
+pub fn set_link_limit(storage: &mut dyn Storage, limit: u32) -> Result<(), ContractError> {
+    Ok(LINK_LIMIT.save(storage, &limit)?)
+}
+
+/// Sei accounts keyed by the lowercase `0x` address of the same secp256k1 key.
+pub const EVM_ADDRESSES: Map<&str, Addr> = Map::new("evm_addresses");
+
+/// Reverse lookup of `EVM_ADDRESSES`.
+pub const SEI_EVM_ADDRESSES: Map<&Addr, String> = Map::new("sei_evm_addresses");
+
+pub fn save_evm_address(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    evm_address: &str,
+) -> Result<(), ContractError> {
+    EVM_ADDRESSES.save(storage, evm_address, address)?;
+    Ok(SEI_EVM_ADDRESSES.save(storage, address, &evm_address.to_string())?)
+}
+
+pub fn get_evm_account(
+    storage: &dyn Storage,
+    evm_address: &str,
+) -> Result<Option<Addr>, ContractError> {
+    Ok(EVM_ADDRESSES.may_load(storage, evm_address)?)
+}
+
+pub fn get_evm_address(
+    storage: &dyn Storage,
+    address: &Addr,
+) -> Result<Option<String>, ContractError> {
+    Ok(SEI_EVM_ADDRESSES.may_load(storage, address)?)
+}
+
+#[cw_serde]
+pub struct DenyEntry {
+    pub reason: String,
+    pub added_by: Addr,
+    pub added_at: Timestamp,
+}
+
+/// Addresses blocked from being attested, approving and passing `verify`.
+pub const DENYLIST: Map<&Addr, DenyEntry> = Map::new("denylist");
+
+pub fn save_deny_entry(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    entry: &DenyEntry,
+) -> Result<(), ContractError> {
+    Ok(DENYLIST.save(storage, address, entry)?)
+}
+
+pub fn remove_deny_entry(storage: &mut dyn Storage, address: &Addr) {
+    DENYLIST.remove(storage, address)
+}
+
+pub fn get_deny_entry(
+    storage: &dyn Storage,
+    address: &Addr,
+) -> Result<Option<DenyEntry>, ContractError> {
+    Ok(DENYLIST.may_load(storage, address)?)
+}
+
+pub fn list_denylist(
+    storage: &dyn Storage,
+    start_after: Option<&Addr>,
+    limit: usize,
+) -> Result<Vec<(Addr, DenyEntry)>, ContractError> {
+    let start = start_after.map(Bound::exclusive);
+    let entries = DENYLIST
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(entries)
+}
+
+/// External contract answering `msg::ScreeningQueryMsg`, consulted after the
+/// local denylist.
+pub const SCREENING_CONTRACT: Item<Addr> = Item::new("screening_contract");
+
+pub fn get_screening_contract(storage: &dyn Storage) -> Result<Option<Addr>, ContractError> {
+    Ok(SCREENING_CONTRACT.may_load(storage)?)
+}
+
+pub fn set_screening_contract(
+    storage: &mut dyn Storage,
+    contract: Option<&Addr>,
+) -> Result<(), ContractError> {
+    match contract {
+        Some(contract) => SCREENING_CONTRACT.save(storage, contract)?,
+        None => SCREENING_CONTRACT.remove(storage),
+    }
+    Ok(())
+}
+
+pub const DEFAULT_BULK_LIMIT: u32 = 20;
+
+pub const BULK_LIMIT: Item<u32> = Item::new("bulk_limit");
+
+pub fn get_bulk_limit(storage: &dyn Storage) -> Result<u32, ContractError> {
+    Ok(BULK_LIMIT.may_load(storage)?.unwrap_or(DEFAULT_BULK_LIMIT))
+}
+
+pub fn set_bulk_limit(storage: &mut dyn Storage, limit: u32) -> Result<(), ContractError> {
+    Ok(BULK_LIMIT.save(storage, &limit)?)
+}
+
+pub const TOKEN_NAME: &str = "zkMe Identity Soulbound Token";
+pub const TOKEN_SYMBOL: &str = "ZIS";
+
+pub const TOKEN_BASE_URI: Item<String> = Item::new("token_base_uri");
+
+pub fn get_token_base_uri(storage: &dyn Storage) -> Result<String, ContractError> {
+    if TOKEN_BASE_URI.exists(storage) {
+        Ok(TOKEN_BASE_URI.load(storage)?)
+    } else {
+        Ok("".to_string())
+    }
+}
+
+pub fn set_token_base_uri(storage: &mut dyn Storage, new_uri: String) -> Result<(), ContractError> {
+    Ok(TOKEN_BASE_URI.save(storage, &new_uri)?)
+}
+
+#[cw_serde]
+pub struct KycData {
+    pub key: String,
+    pub validity: Timestamp,
+    pub data: String,
+    pub questions: Vec<String>,
+}
+
+pub const KYC_MAP: Map<&TokenId, KycData> = Map::new("kyc_map");
+
+pub fn has_kyc(storage: &dyn Storage, k: &TokenId) -> bool {
+    KYC_MAP.has(storage, k)
+}
+
+/// Tokens ordered by the expiry of their KYC data, in nanoseconds.
+pub const KYC_EXPIRY: Map<(u64, TokenId), bool> = Map::new("kyc_expiry");
+
+pub fn save_kyc(storage: &mut dyn Storage, k: &TokenId, v: KycData) -> Result<(), ContractError> {
+    if let Some(old) = get_kyc(storage, k)? {
+        KYC_EXPIRY.remove(storage, (old.validity.nanos(), *k));
+    }
+    KYC_EXPIRY.save(storage, (v.validity.nanos(), *k), &true)?;
+
+    if has_kyc(storage, k) {
+        let _ = KYC_MAP.update(storage, k, move |_kyc| -> Result<_, ContractError> {
+            Ok(v)
+        })?;
+        Ok(())
+    } else {
+        Ok(KYC_MAP.save(storage, k, &v)?)
+    }
+}
+
+pub fn get_kyc(storage: &dyn Storage, k: &TokenId) -> Result<Option<KycData>, ContractError> {
+    Ok(KYC_MAP.may_load(storage, k)?)
+}
+
+pub fn remove_kyc(storage: &mut dyn Storage, k: &TokenId) -> Result<(), ContractError> {
+    if let Some(kyc) = get_kyc(storage, k)? {
+        KYC_EXPIRY.remove(storage, (kyc.validity.nanos(), *k));
+        KYC_MAP.remove(storage, k);
+    }
+    Ok(())
+}
+
+/// Last token visited by an unfinished KYC expiry backfill.
+pub const KYC_EXPIRY_BACKFILL_CURSOR: Item<TokenId> = Item::new("kyc_expiry_backfill_cursor");
+
+/// Indexes up to `limit` more `KYC_MAP` entries written before `KYC_EXPIRY`
+/// existed. Returns the number of tokens added and whether entries remain.
+pub fn backfill_kyc_expiry(
+    storage: &mut dyn Storage,
+    limit: usize,
+) -> Result<(u64, bool), ContractError> {
+    let cursor = KYC_EXPIRY_BACKFILL_CURSOR.may_load(storage)?;
+    let min = cursor.as_ref().map(Bound::exclusive);
+    let mut page = KYC_MAP
+        .range_raw(storage, min, None, Order::Ascending)
+        .take(limit + 1)
+        .map(|item| item.and_then(|(key, kyc)| Ok((kyc.validity.nanos(), TokenId::from_vec(key)?))))
+        .collect::<StdResult<Vec<_>>>()?;
+    let remaining = page.len() > limit;
+    page.truncate(limit);
+
+    let mut added = 0;
+    for key in &page {
+        if !KYC_EXPIRY.has(storage, *key) {
+            KYC_EXPIRY.save(storage, *key, &true)?;
+            added += 1;
+        }
+    }
+    match page.pop() {
+        Some((_, token_id)) if remaining => KYC_EXPIRY_BACKFILL_CURSOR.save(storage, &token_id)?,
+        _ => KYC_EXPIRY_BACKFILL_CURSOR.remove(storage),
+    }
+    Ok((added, remaining))
+}
+
+/// Tokens whose KYC data expires before `before`, soonest first.
+pub fn list_expiring(
+    storage: &dyn Storage,
+    before: Timestamp,
+    start_after: Option<(Timestamp, TokenId)>,
+    limit: usize,
+) -> Result<Vec<(TokenId, Timestamp)>, ContractError> {
+    let min =
+        start_after.map(|(validity, token_id)| Bound::exclusive((validity.nanos(), token_id)));
+    let max = Some(Bound::exclusive((before.nanos(), 0)));
+
+    let tokens = KYC_EXPIRY
+        .keys(storage, min, max, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(nanos, token_id)| (token_id, Timestamp::from_nanos(nanos))))
+        .collect::<StdResult<_>>()?;
+    Ok(tokens)
+}
+
+/// Seconds after expiry during which KYC data is still accepted by `verify`.
+pub const GRACE_PERIOD: Item<u64> = Item::new("grace_period");
+
+pub fn get_grace_period(storage: &dyn Storage) -> Result<u64, ContractError> {
+    Ok(GRACE_PERIOD.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn set_grace_period(storage: &mut dyn Storage, seconds: u64) -> Result<(), ContractError> {
+    Ok(GRACE_PERIOD.save(storage, &seconds)?)
+}
+
+/// Seconds KYC data is retained once its grace period is over, before it can
+/// be pruned.
+pub const RETENTION_PERIOD: Item<u64> = Item::new("retention_period");
+
+pub fn get_retention_period(storage: &dyn Storage) -> Result<u64, ContractError> {
+    Ok(RETENTION_PERIOD.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn set_retention_period(storage: &mut dyn Storage, seconds: u64) -> Result<(), ContractError> {
+    Ok(RETENTION_PERIOD.save(storage, &seconds)?)
+}
+
+#[cw_serde]
+pub struct Erasure {
+    pub token_id: TokenId,
+    pub erased_at: Timestamp,
+    pub token_kept: bool,
+}
+
+/// Tombstones left by `EraseMyData`, keyed by the former holder. Cleared when
+/// the holder is attested again or receives new KYC data.
+pub const ERASURES: Map<&Addr, Erasure> = Map::new("erasures");
+
+pub fn save_erasure(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    erasure: &Erasure,
+) -> Result<(), ContractError> {
+    Ok(ERASURES.save(storage, owner, erasure)?)
+}
+
+pub fn get_erasure(storage: &dyn Storage, owner: &Addr) -> Result<Option<Erasure>, ContractError> {
+    Ok(ERASURES.may_load(storage, owner)?)
+}
+
+pub fn remove_erasure(storage: &mut dyn Storage, owner: &Addr) {
+    ERASURES.remove(storage, owner)
+}
+
+#[cw_serde]
+pub struct FreezeInfo {
+    pub reason: String,
+    pub frozen_by: Addr,
+    pub frozen_at: Timestamp,
+}
+
+/// Indexes added after launch that `ExecuteMsg::Backfill` fills in for data
+/// stored before them.
+#[cw_serde]
+pub enum BackfillIndex {
+    Approvals,
+    KycExpiry,
+    KycCopies,
+}
+
+#[cw_serde]
+pub enum FreezeAction {
+    Freeze,
+    Unfreeze,
+}
+
+#[cw_serde]
+pub struct FreezeRecord {
+    pub action: FreezeAction,
+    pub reason: String,
+    pub by: Addr,
+    pub at: Timestamp,
+}
+
+pub const FROZEN_MAP: Map<&TokenId, FreezeInfo> = Map::new("frozen_map");
+
+pub const FREEZE_HISTORY: Map<&TokenId, Vec<FreezeRecord>> = Map::new("freeze_history");
+
+pub fn is_frozen(storage: &dyn Storage, token_id: &TokenId) -> bool {
+    FROZEN_MAP.has(storage, token_id)
+}
+
+pub fn get_freeze_info(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+) -> Result<Option<FreezeInfo>, ContractError> {
+    Ok(FROZEN_MAP.may_load(storage, token_id)?)
+}
+
+pub fn freeze_token(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    info: FreezeInfo,
+) -> Result<(), ContractError> {
+    push_freeze_record(
+        storage,
+        token_id,
+        FreezeRecord {
+            action: FreezeAction::Freeze,
+            reason: info.reason.clone(),
+            by: info.frozen_by.clone(),
+            at: info.frozen_at,
+        },
+    )?;
+    Ok(FROZEN_MAP.save(storage, token_id, &info)?)
+}
+
+pub fn unfreeze_token(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    record: FreezeRecord,
+) -> Result<(), ContractError> {
+    push_freeze_record(storage, token_id, record)?;
+    FROZEN_MAP.remove(storage, token_id);
+    Ok(())
+}
+
+fn push_freeze_record(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    record: FreezeRecord,
+) -> Result<(), ContractError> {
+    FREEZE_HISTORY.update(
+        storage,
+        token_id,
+        move |history| -> Result<_, ContractError> {
+            let mut history = history.unwrap_or_default();
+            history.push(record);
+            Ok(history)
+        },
+    )?;
+    Ok(())
+}
+
+pub fn get_freeze_history(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+) -> Result<Vec<FreezeRecord>, ContractError> {
+    Ok(FREEZE_HISTORY
+        .may_load(storage, token_id)?
+        .unwrap_or_default())
+}
+
+#[cw_serde]
+pub struct FieldCommitment {
+    pub field: String,
+    pub commitment: Binary,
+}
+
+/// SHA-256 commitments stored in privacy mode instead of the plaintext
+/// `KycData.data`. Each field commitment is `sha256(salt || field || 0x00 || value)`
+/// with a 32 byte salt.
+#[cw_serde]
+pub struct KycCommitment {
+    pub data_commitment: Binary,
+    pub field_commitments: Vec<FieldCommitment>,
+}
+
+pub const KYC_COMMITMENT_MAP: Map<&TokenId, KycCommitment> = Map::new("kyc_commitment_map");
+
+pub fn save_kyc_commitment(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    commitment: &KycCommitment,
+) -> Result<(), ContractError> {
+    Ok(KYC_COMMITMENT_MAP.save(storage, token_id, commitment)?)
+}
+
+pub fn get_kyc_commitment(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+) -> Result<Option<KycCommitment>, ContractError> {
+    Ok(KYC_COMMITMENT_MAP.may_load(storage, token_id)?)
+}
+
+pub fn remove_kyc_commitment(storage: &mut dyn Storage, token_id: &TokenId) {
+    KYC_COMMITMENT_MAP.remove(storage, token_id)
+}
+
+/// Merkle root over a token's attribute claims, see `crypto::verify_merkle_proof`.
+pub const ATTRIBUTE_ROOT_MAP: Map<&TokenId, Binary> = Map::new("attribute_root_map");
+
+pub fn remove_attribute_root(storage: &mut dyn Storage, token_id: &TokenId) {
+    ATTRIBUTE_ROOT_MAP.remove(storage, token_id)
+}
+
+pub fn save_attribute_root(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    root: &Binary,
+) -> Result<(), ContractError> {
+    Ok(ATTRIBUTE_ROOT_MAP.save(storage, token_id, root)?)
+}
+
+pub fn get_attribute_root(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+) -> Result<Option<Binary>, ContractError> {
+    Ok(ATTRIBUTE_ROOT_MAP.may_load(storage, token_id)?)
+}
+
+/// Groth16 verifying keys registered per question type.
+pub const VERIFYING_KEYS: Map<&str, Binary> = Map::new("verifying_keys");
+
+/// Bumped on every registration so claims proven under a replaced key can be
+/// told apart.
+pub const VERIFYING_KEY_VERSIONS: Map<&str, u32> = Map::new("verifying_key_versions");
+
+/// Stores `vk` for `question` and returns its new version.
+pub fn save_verifying_key(
+    storage: &mut dyn Storage,
+    question: &str,
+    vk: &Binary,
+) -> Result<u32, ContractError> {
+    let version = get_verifying_key_version(storage, question)? + 1;
+    VERIFYING_KEY_VERSIONS.save(storage, question, &version)?;
+    VERIFYING_KEYS.save(storage, question, vk)?;
+    Ok(version)
+}
+
+pub fn get_verifying_key_version(
+    storage: &dyn Storage,
+    question: &str,
+) -> Result<u32, ContractError> {
+    Ok(VERIFYING_KEY_VERSIONS
+        .may_load(storage, question)?
+        .unwrap_or_default())
 }
 
-pub fn set_questions(
+pub fn get_verifying_key(
+    storage: &dyn Storage,
+    question: &str,
+) -> Result<Option<Binary>, ContractError> {
+    Ok(VERIFYING_KEYS.may_load(storage, question)?)
+}
+
+#[cw_serde]
+pub struct VerifiedClaim {
+    pub submitter: Addr,
+    pub verified_at: Timestamp,
+    /// Version of the verifying key the proof was checked against.
+    pub key_version: u32,
+}
+
+/// Questions a token satisfied by an on-chain verified proof, keyed `(token_id, question)`.
+pub const VERIFIED_CLAIMS: Map<(TokenId, &str), VerifiedClaim> = Map::new("verified_claims");
+
+pub fn save_verified_claim(
     storage: &mut dyn Storage,
-    cooperator: &Addr,
-    questions: Vec<String>,
+    token_id: TokenId,
+    question: &str,
+    claim: &VerifiedClaim,
 ) -> Result<(), ContractError> {
-    if has_questions(storage, cooperator) {
-        let _ = CONF_QUESTIONS.update(
-            storage,
-            cooperator,
-            move |_old_questions| -> Result<_, ContractError> { Ok(questions) },
-        );
-        Ok(())
-    } else {
-        Ok(CONF_QUESTIONS.save(storage, cooperator, &questions)?)
-    }
+    Ok(VERIFIED_CLAIMS.save(storage, (token_id, question), claim)?)
 }
 
-pub fn get_questions(
+/// The claim of a token for `question`, unless it was proven under a verifying
+/// key that has since been replaced.
+pub fn get_verified_claim(
     storage: &dyn Storage,
-    cooperator: &Addr,
-) -> Result<Option<Vec<String>>, ContractError> {
-    if has_questions(storage, cooperator) {
-        Ok(Some(CONF_QUESTIONS.load(storage, cooperator)?))
-    } else {
-        Ok(None)
-    }
+    token_id: TokenId,
+    question: &str,
+) -> Result<Option<VerifiedClaim>, ContractError> {
+    let version = get_verifying_key_version(storage, question)?;
+    Ok(VERIFIED_CLAIMS
+        .may_load(storage, (token_id, question))?
+        .filter(|claim| claim.key_version == version))
 }
 
-pub const COUNTER: Item<TokenId> = Item::new("token_id");
+pub fn remove_verified_claims(
+    storage: &mut dyn Storage,
+    token_id: TokenId,
+) -> Result<(), ContractError> {
+    let questions = VERIFIED_CLAIMS
+        .prefix(token_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for question in &questions {
+        VERIFIED_CLAIMS.remove(storage, (token_id, question));
+    }
+    Ok(())
+}
 
-pub fn initialize_counter(storage: &mut dyn Storage) -> Result<(), ContractError> {
-    Ok(COUNTER.save(storage, &1)?)
+pub fn has_verified_claim(storage: &dyn Storage, token_id: TokenId, question: &str) -> bool {
+    matches!(get_verified_claim(storage, token_id, question), Ok(Some(_)))
 }
 
-pub fn increase_counter(storage: &mut dyn Storage) -> Result<(), ContractError> {
-    let _ = COUNTER.update(storage, |counter| -> Result<_, ContractError> {
-        Ok(counter + 1)
-    })?;
-    Ok(())
+#[cw_serde]
+pub enum KeyAlgorithm {
+    Secp256k1,
+    Ed25519,
 }
 
-pub fn get_token_id(storage: &dyn Storage) -> Result<TokenId, ContractError> {
-    Ok(COUNTER.load(storage)?)
+#[cw_serde]
+pub struct AttestorKey {
+    pub algorithm: KeyAlgorithm,
+    pub pubkey: Binary,
+    pub added_at: Timestamp,
+    pub revoked_at: Option<Timestamp>,
 }
 
-pub const OWNER_MAP: Map<&TokenId, Addr> = Map::new("owner_map");
+/// Keys trusted to sign off-chain KYC attestations. Revoked keys are kept so
+/// the key set stays auditable; rotation is adding a new key and revoking the old.
+pub const ATTESTOR_KEYS: Map<&str, AttestorKey> = Map::new("attestor_keys");
 
-pub fn has_owner(storage: &dyn Storage, k: &TokenId) -> Result<bool, ContractError> {
-    Ok(OWNER_MAP.has(storage, k))
+pub fn save_attestor_key(
+    storage: &mut dyn Storage,
+    key_id: &str,
+    key: &AttestorKey,
+) -> Result<(), ContractError> {
+    Ok(ATTESTOR_KEYS.save(storage, key_id, key)?)
 }
 
-pub fn save_owner(storage: &mut dyn Storage, k: &TokenId, v: &Addr) -> Result<(), ContractError> {
-    if has_owner(storage, k)?.not() {
-        OWNER_MAP.save(storage, k, v)?;
-    }
-    Ok(())
+pub fn get_attestor_key(
+    storage: &dyn Storage,
+    key_id: &str,
+) -> Result<Option<AttestorKey>, ContractError> {
+    Ok(ATTESTOR_KEYS.may_load(storage, key_id)?)
 }
 
-pub fn get_owner(storage: &dyn Storage, k: &TokenId) -> Result<Option<Addr>, ContractError> {
-    Ok(OWNER_MAP.may_load(storage, k)?)
+pub fn list_attestor_keys(
+    storage: &dyn Storage,
+    start_after: Option<&str>,
+    limit: usize,
+) -> Result<Vec<(String, AttestorKey)>, ContractError> {
+    let start = start_after.map(Bound::exclusive);
+    let keys = ATTESTOR_KEYS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(keys)
 }
 
-pub const TOKEN_MAP: Map<&Addr, TokenId> = Map::new("token_map");
+/// Last attestation nonce accepted per token, attestations must use a higher one.
+pub const ATTESTATION_NONCES: Map<&TokenId, u64> = Map::new("attestation_nonces");
 
-pub fn has_token(storage: &dyn Storage, k: &Addr) -> bool {
-    TOKEN_MAP.has(storage, k)
+pub fn get_attestation_nonce(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+) -> Result<Option<u64>, ContractError> {
+    Ok(ATTESTATION_NONCES.may_load(storage, token_id)?)
 }
 
-pub fn save_token(storage: &mut dyn Storage, k: &Addr, v: &TokenId) -> Result<(), ContractError> {
-    Ok(TOKEN_MAP.save(storage, k, v)?)
+pub fn remove_attestation_nonce(storage: &mut dyn Storage, token_id: &TokenId) {
+    ATTESTATION_NONCES.remove(storage, token_id);
 }
 
-pub fn get_token(storage: &dyn Storage, k: &Addr) -> Result<Option<TokenId>, ContractError> {
-    Ok(TOKEN_MAP.may_load(storage, k)?)
+pub fn save_attestation_nonce(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    nonce: u64,
+) -> Result<(), ContractError> {
+    Ok(ATTESTATION_NONCES.save(storage, token_id, &nonce)?)
 }
 
-pub const TOKEN_NAME: &'static str = "zkMe Identity Soulbound Token";
-pub const TOKEN_SYMBOL: &'static str = "ZIS";
+#[cw_serde]
+pub struct IssuerInfo {
+    pub name: String,
+    pub trust_level: u8,
+    pub added_at: Timestamp,
+}
 
-pub const TOKEN_BASE_URI: Item<String> = Item::new("token_base_uri");
+/// KYC providers besides zkMe that may attach attestations to tokens.
+pub const ISSUERS: Map<&Addr, IssuerInfo> = Map::new("issuers");
 
-pub fn get_token_base_uri(storage: &dyn Storage) -> Result<String, ContractError> {
-    if TOKEN_BASE_URI.exists(storage) {
-        Ok(TOKEN_BASE_URI.load(storage)?)
-    } else {
-        Ok("".to_string())
-    }
+pub fn save_issuer(
+    storage: &mut dyn Storage,
+    issuer: &Addr,
+    info: &IssuerInfo,
+) -> Result<(), ContractError> {
+    Ok(ISSUERS.save(storage, issuer, info)?)
 }
 
-pub fn set_token_base_uri(storage: &mut dyn Storage, new_uri: String) -> Result<(), ContractError> {
-    Ok(TOKEN_BASE_URI.save(storage, &new_uri)?)
+pub fn remove_issuer(storage: &mut dyn Storage, issuer: &Addr) {
+    ISSUERS.remove(storage, issuer)
+}
+
+pub fn get_issuer(
+    storage: &dyn Storage,
+    issuer: &Addr,
+) -> Result<Option<IssuerInfo>, ContractError> {
+    Ok(ISSUERS.may_load(storage, issuer)?)
+}
+
+pub fn list_issuers(
+    storage: &dyn Storage,
+    start_after: Option<&Addr>,
+    limit: usize,
+) -> Result<Vec<(Addr, IssuerInfo)>, ContractError> {
+    let start = start_after.map(Bound::exclusive);
+    let issuers = ISSUERS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(issuers)
 }
 
 #[cw_serde]
-pub struct KycData {
-    pub key: String,
+pub struct IssuerAttestation {
     pub validity: Timestamp,
     pub data: String,
     pub questions: Vec<String>,
+    pub issued_at: Timestamp,
 }
 
-pub const KYC_MAP: Map<&TokenId, KycData> = Map::new("kyc_map");
+/// Attestations by registered issuers, keyed `(token_id, issuer)`.
+pub const ISSUER_ATTESTATIONS: Map<(TokenId, &Addr), IssuerAttestation> =
+    Map::new("issuer_attestations");
 
-pub fn has_kyc(storage: &dyn Storage, k: &TokenId) -> bool {
-    KYC_MAP.has(storage, k)
+/// Issuer attestations ordered by validity, keyed `(validity nanos, token_id, issuer)`.
+pub const ATTESTATION_EXPIRY: Map<(u64, TokenId, &Addr), bool> = Map::new("attestation_expiry");
+
+pub fn save_issuer_attestation(
+    storage: &mut dyn Storage,
+    token_id: TokenId,
+    issuer: &Addr,
+    attestation: &IssuerAttestation,
+) -> Result<(), ContractError> {
+    remove_issuer_attestation(storage, token_id, issuer)?;
+    ATTESTATION_EXPIRY.save(
+        storage,
+        (attestation.validity.nanos(), token_id, issuer),
+        &true,
+    )?;
+    Ok(ISSUER_ATTESTATIONS.save(storage, (token_id, issuer), attestation)?)
 }
 
-pub fn save_kyc(storage: &mut dyn Storage, k: &TokenId, v: KycData) -> Result<(), ContractError> {
-    if has_kyc(storage, k) {
-        let _ = KYC_MAP.update(storage, k, move |_kyc| -> Result<_, ContractError> {
-            Ok(v)
-        })?;
-        Ok(())
-    } else {
-        Ok(KYC_MAP.save(storage, &k, &v)?)
+pub fn remove_issuer_attestation(
+    storage: &mut dyn Storage,
+    token_id: TokenId,
+    issuer: &Addr,
+) -> Result<(), ContractError> {
+    if let Some(attestation) = get_issuer_attestation(storage, token_id, issuer)? {
+        ATTESTATION_EXPIRY.remove(storage, (attestation.validity.nanos(), token_id, issuer));
+        ISSUER_ATTESTATIONS.remove(storage, (token_id, issuer));
     }
+    Ok(())
 }
 
-pub fn get_kyc(storage: &dyn Storage, k: &TokenId) -> Result<Option<KycData>, ContractError> {
-    Ok(KYC_MAP.may_load(storage, k)?)
+/// Issuer attestations whose validity ended before `before`, soonest first.
+pub fn list_expired_attestations(
+    storage: &dyn Storage,
+    before: Timestamp,
+    limit: usize,
+) -> Result<Vec<(TokenId, Addr, Timestamp)>, ContractError> {
+    let lowest = Addr::unchecked("");
+    let max = Some(Bound::exclusive((before.nanos(), 0, &lowest)));
+    let attestations = ATTESTATION_EXPIRY
+        .keys(storage, None, max, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(nanos, token_id, issuer)| (token_id, issuer, Timestamp::from_nanos(nanos)))
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(attestations)
+}
+
+pub fn get_issuer_attestation(
+    storage: &dyn Storage,
+    token_id: TokenId,
+    issuer: &Addr,
+) -> Result<Option<IssuerAttestation>, ContractError> {
+    Ok(ISSUER_ATTESTATIONS.may_load(storage, (token_id, issuer))?)
+}
+
+pub fn has_issuer_attestation(storage: &dyn Storage, token_id: TokenId, issuer: &Addr) -> bool {
+    ISSUER_ATTESTATIONS.has(storage, (token_id, issuer))
+}
+
+pub fn list_issuer_attestations(
+    storage: &dyn Storage,
+    token_id: TokenId,
+) -> Result<Vec<(Addr, IssuerAttestation)>, ContractError> {
+    let attestations = ISSUER_ATTESTATIONS
+        .prefix(token_id)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(attestations)
+}
+
+#[cw_serde]
+pub struct KycMetadata {
+    pub updated_by: Addr,
+    pub updated_at: Timestamp,
+    pub validity: Timestamp,
+    pub questions: Vec<String>,
+}
+
+/// Metadata of every KYC write for a token, without the key or data payload.
+pub const KYC_HISTORY: Map<&TokenId, Vec<KycMetadata>> = Map::new("kyc_history");
+
+pub fn push_kyc_history(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    metadata: KycMetadata,
+) -> Result<(), ContractError> {
+    KYC_HISTORY.update(
+        storage,
+        token_id,
+        move |history| -> Result<_, ContractError> {
+            let mut history = history.unwrap_or_default();
+            history.push(metadata);
+            Ok(history)
+        },
+    )?;
+    Ok(())
+}
+
+pub fn remove_kyc_history(storage: &mut dyn Storage, token_id: &TokenId) {
+    KYC_HISTORY.remove(storage, token_id);
+}
+
+pub fn get_kyc_history(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+) -> Result<Vec<KycMetadata>, ContractError> {
+    Ok(KYC_HISTORY.may_load(storage, token_id)?.unwrap_or_default())
+}
+
+#[cw_serde]
+pub struct OperatorAction {
+    pub action: String,
+    pub operator: Addr,
+    pub at: Timestamp,
+}
+
+/// Privileged actions taken on a token by operators and inspectors.
+pub const OPERATOR_ACTIONS: Map<&TokenId, Vec<OperatorAction>> = Map::new("operator_actions");
+
+pub fn push_operator_action(
+    storage: &mut dyn Storage,
+    token_id: &TokenId,
+    action: &str,
+    operator: &Addr,
+    at: Timestamp,
+) -> Result<(), ContractError> {
+    let entry = OperatorAction {
+        action: action.to_string(),
+        operator: operator.clone(),
+        at,
+    };
+    OPERATOR_ACTIONS.update(
+        storage,
+        token_id,
+        move |actions| -> Result<_, ContractError> {
+            let mut actions = actions.unwrap_or_default();
+            actions.push(entry);
+            Ok(actions)
+        },
+    )?;
+    Ok(())
+}
+
+pub fn get_operator_actions(
+    storage: &dyn Storage,
+    token_id: &TokenId,
+) -> Result<Vec<OperatorAction>, ContractError> {
+    Ok(OPERATOR_ACTIONS
+        .may_load(storage, token_id)?
+        .unwrap_or_default())
+}
+
+#[cw_serde]
+pub struct AuditEntry {
+    pub inspector: Addr,
+    pub token_id: TokenId,
+    pub reason: String,
+    pub at: Timestamp,
+}
+
+pub const AUDIT_COUNTER: Item<u64> = Item::new("audit_counter");
+
+/// Every inspection performed through `InspectToken`, in order.
+pub const AUDIT_LOG: Map<u64, AuditEntry> = Map::new("audit_log");
+
+pub fn push_audit_entry(
+    storage: &mut dyn Storage,
+    entry: &AuditEntry,
+) -> Result<u64, ContractError> {
+    let id = AUDIT_COUNTER.may_load(storage)?.unwrap_or_default() + 1;
+    AUDIT_COUNTER.save(storage, &id)?;
+    AUDIT_LOG.save(storage, id, entry)?;
+    Ok(id)
+}
+
+pub fn list_audit_log(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: usize,
+) -> Result<Vec<(u64, AuditEntry)>, ContractError> {
+    let start = start_after.map(Bound::exclusive);
+    let entries = AUDIT_LOG
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(entries)
+}
+
+/// Permit names revoked by their signer, keyed `(signer, permit_name)`.
+pub const REVOKED_PERMITS: Map<(&Addr, &str), bool> = Map::new("revoked_permits");
+
+pub fn is_permit_revoked(storage: &dyn Storage, signer: &Addr, permit_name: &str) -> bool {
+    REVOKED_PERMITS.has(storage, (signer, permit_name))
+}
+
+pub fn revoke_permit(
+    storage: &mut dyn Storage,
+    signer: &Addr,
+    permit_name: &str,
+) -> Result<(), ContractError> {
+    Ok(REVOKED_PERMITS.save(storage, (signer, permit_name), &true)?)
 }
 
 pub const PU_MAP: Map<(&Addr, &Addr), TokenId> = Map::new("pu_map");
@@ -242,6 +1618,182 @@ pub fn remove_pu(storage: &mut dyn Storage, cooperator: &Addr, user: &Addr) {
     PU_MAP.remove(storage, (cooperator, user))
 }
 
+#[cw_serde]
+pub struct ApprovalData {
+    pub user: Addr,
+    pub token_id: TokenId,
+    pub approved_at: Timestamp,
+}
+
+pub struct ApprovalIndexes<'a> {
+    pub user: MultiIndex<'a, Addr, ApprovalData, (&'a Addr, &'a Addr)>,
+}
+
+impl<'a> IndexList<ApprovalData> for ApprovalIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<ApprovalData>> + '_> {
+        let v: Vec<&dyn Index<ApprovalData>> = vec![&self.user];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Approvals keyed by `(cooperator, user)`, indexed by user for the reverse lookup.
+pub fn approvals<'a>() -> IndexedMap<'a, (&'a Addr, &'a Addr), ApprovalData, ApprovalIndexes<'a>> {
+    let indexes = ApprovalIndexes {
+        user: MultiIndex::new(
+            |_pk, approval| approval.user.clone(),
+            "approvals",
+            "approvals__user",
+        ),
+    };
+    IndexedMap::new("approvals", indexes)
+}
+
+pub fn save_approval(
+    storage: &mut dyn Storage,
+    cooperator: &Addr,
+    user: &Addr,
+    token_id: TokenId,
+    approved_at: Timestamp,
+) -> Result<(), ContractError> {
+    let approval = ApprovalData {
+        user: user.clone(),
+        token_id,
+        approved_at,
+    };
+    Ok(approvals().save(storage, (cooperator, user), &approval)?)
+}
+
+/// Last `PU_MAP` key visited by an unfinished approvals backfill.
+pub const APPROVALS_BACKFILL_CURSOR: Item<(Addr, Addr)> = Item::new("approvals_backfill_cursor");
+
+/// Indexes up to `limit` more `PU_MAP` entries written before `approvals()`
+/// existed, stamping them with `approved_at`. Returns the number of approvals
+/// added and whether entries remain.
+pub fn backfill_approvals(
+    storage: &mut dyn Storage,
+    approved_at: Timestamp,
+    limit: usize,
+) -> Result<(u64, bool), ContractError> {
+    let cursor = APPROVALS_BACKFILL_CURSOR.may_load(storage)?;
+    let min = cursor
+        .as_ref()
+        .map(|(cooperator, user)| Bound::exclusive((cooperator, user)));
+    let mut page = PU_MAP
+        .range(storage, min, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    let remaining = page.len() > limit;
+    page.truncate(limit);
+
+    let mut added = 0;
+    for ((cooperator, user), token_id) in &page {
+        if !approvals().has(storage, (cooperator, user)) {
+            save_approval(storage, cooperator, user, *token_id, approved_at)?;
+            added += 1;
+        }
+    }
+    match page.pop() {
+        Some((last, _)) if remaining => APPROVALS_BACKFILL_CURSOR.save(storage, &last)?,
+        _ => APPROVALS_BACKFILL_CURSOR.remove(storage),
+    }
+    Ok((added, remaining))
+}
+
+pub fn remove_approval(
+    storage: &mut dyn Storage,
+    cooperator: &Addr,
+    user: &Addr,
+) -> Result<(), ContractError> {
+    Ok(approvals().remove(storage, (cooperator, user))?)
+}
+
+pub fn list_approvals_by_user(
+    storage: &dyn Storage,
+    user: &Addr,
+    start_after: Option<&Addr>,
+    limit: usize,
+) -> Result<Vec<(Addr, ApprovalData)>, ContractError> {
+    let start = start_after.map(|cooperator| Bound::exclusive((cooperator, user)));
+    let approvals = approvals()
+        .idx
+        .user
+        .prefix(user.clone())
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|((cooperator, _user), approval)| (cooperator, approval)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(approvals)
+}
+
+#[cw_serde]
+pub struct DelegateGrant {
+    pub expires_at: Option<Timestamp>,
+    pub cooperators: Option<Vec<Addr>>,
+}
+
+impl DelegateGrant {
+    // kept as `map_or` so older toolchains build it
+    #[allow(clippy::unnecessary_map_or)]
+    pub fn allows(&self, cooperator: &Addr, now: Timestamp) -> bool {
+        self.expires_at.map_or(true, |expires_at| now < expires_at)
+            && self
+                .cooperators
+                .as_ref()
+                .map_or(true, |cooperators| cooperators.contains(cooperator))
+    }
+}
+
+/// Delegates allowed to approve and revoke on behalf of a token owner, keyed `(owner, delegate)`.
+pub const DELEGATES: Map<(&Addr, &Addr), DelegateGrant> = Map::new("delegates");
+
+pub fn save_delegate(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    delegate: &Addr,
+    grant: &DelegateGrant,
+) -> Result<(), ContractError> {
+    Ok(DELEGATES.save(storage, (owner, delegate), grant)?)
+}
+
+pub fn remove_delegate(storage: &mut dyn Storage, owner: &Addr, delegate: &Addr) {
+    DELEGATES.remove(storage, (owner, delegate))
+}
+
+pub fn get_delegate(
+    storage: &dyn Storage,
+    owner: &Addr,
+    delegate: &Addr,
+) -> Result<Option<DelegateGrant>, ContractError> {
+    Ok(DELEGATES.may_load(storage, (owner, delegate))?)
+}
+
+pub fn is_delegate_for(
+    storage: &dyn Storage,
+    owner: &Addr,
+    delegate: &Addr,
+    cooperator: &Addr,
+    now: Timestamp,
+) -> Result<bool, ContractError> {
+    Ok(get_delegate(storage, owner, delegate)?
+        .map(|grant| grant.allows(cooperator, now))
+        .unwrap_or(false))
+}
+
+pub fn list_delegates(
+    storage: &dyn Storage,
+    owner: &Addr,
+    start_after: Option<&Addr>,
+    limit: usize,
+) -> Result<Vec<(Addr, DelegateGrant)>, ContractError> {
+    let start = start_after.map(Bound::exclusive);
+    let delegates = DELEGATES
+        .prefix(owner)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(delegates)
+}
+
 pub const APPROVE_MAP: Map<&Addr, Vec<TokenId>> = Map::new("approve_map");
 
 pub fn get_approved(
@@ -297,6 +1849,9 @@ pub fn remove_approved(
 
 pub const APPROVED_KYC_MAP: Map<(&Addr, TokenId), KycData> = Map::new("approved_kyc_map");
 
+/// Cooperators holding a copy of a token's KYC data in `APPROVED_KYC_MAP`.
+pub const KYC_COPIES: Map<(TokenId, &Addr), bool> = Map::new("kyc_copies");
+
 pub fn has_approved_kyc_data(storage: &dyn Storage, cooperator: &Addr, token_id: TokenId) -> bool {
     APPROVED_KYC_MAP.has(storage, (cooperator, token_id))
 }
@@ -336,10 +1891,92 @@ pub fn save_approved_kyc_data(
             data,
             questions,
         };
+        KYC_COPIES.save(storage, (token_id, cooperator), &true)?;
         Ok(APPROVED_KYC_MAP.save(storage, (cooperator, token_id), &new_kyc)?)
     }
 }
 
+/// Last `APPROVED_KYC_MAP` key visited by an unfinished KYC copies backfill.
+pub const KYC_COPIES_BACKFILL_CURSOR: Item<(Addr, TokenId)> =
+    Item::new("kyc_copies_backfill_cursor");
+
+/// Indexes up to `limit` more `APPROVED_KYC_MAP` entries written before
+/// `KYC_COPIES` existed. Returns the number of copies added and whether
+/// entries remain.
+pub fn backfill_kyc_copies(
+    storage: &mut dyn Storage,
+    limit: usize,
+) -> Result<(u64, bool), ContractError> {
+    let cursor = KYC_COPIES_BACKFILL_CURSOR.may_load(storage)?;
+    let min = cursor
+        .as_ref()
+        .map(|(cooperator, token_id)| Bound::exclusive((cooperator, *token_id)));
+    let mut page = APPROVED_KYC_MAP
+        .keys(storage, min, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    let remaining = page.len() > limit;
+    page.truncate(limit);
+
+    let mut added = 0;
+    for (cooperator, token_id) in &page {
+        if !KYC_COPIES.has(storage, (*token_id, cooperator)) {
+            KYC_COPIES.save(storage, (*token_id, cooperator), &true)?;
+            added += 1;
+        }
+    }
+    match page.pop() {
+        Some(last) if remaining => KYC_COPIES_BACKFILL_CURSOR.save(storage, &last)?,
+        _ => KYC_COPIES_BACKFILL_CURSOR.remove(storage),
+    }
+    Ok((added, remaining))
+}
+
+/// Removes every cooperator copy of a token's KYC data, returning the holders.
+pub fn remove_approved_kyc_copies(
+    storage: &mut dyn Storage,
+    token_id: TokenId,
+) -> Result<Vec<Addr>, ContractError> {
+    let cooperators = KYC_COPIES
+        .prefix(token_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for cooperator in &cooperators {
+        APPROVED_KYC_MAP.remove(storage, (cooperator, token_id));
+        KYC_COPIES.remove(storage, (token_id, cooperator));
+    }
+    Ok(cooperators)
+}
+
+/// Moves the validity of every cooperator copy of a token's KYC data along
+/// with a renewal, returning the cooperators updated.
+pub fn renew_approved_kyc_copies(
+    storage: &mut dyn Storage,
+    token_id: TokenId,
+    validity: Timestamp,
+) -> Result<Vec<Addr>, ContractError> {
+    let cooperators = KYC_COPIES
+        .prefix(token_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for cooperator in &cooperators {
+        APPROVED_KYC_MAP.update(
+            storage,
+            (cooperator, token_id),
+            |copy| -> Result<_, ContractError> {
+                let copy = copy.ok_or(ContractError::UpdatingErrorSlot)?;
+                Ok(KycData { validity, ..copy })
+            },
+        )?;
+    }
+    Ok(cooperators)
+}
+
+pub fn remove_approved_kyc_data(storage: &mut dyn Storage, cooperator: &Addr, token_id: TokenId) {
+    APPROVED_KYC_MAP.remove(storage, (cooperator, token_id));
+    KYC_COPIES.remove(storage, (token_id, cooperator));
+}
+
 pub fn get_approved_kyc_data(
     storage: &dyn Storage,
     cooperator: &Addr,
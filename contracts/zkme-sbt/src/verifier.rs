@@ -0,0 +1,47 @@
+use ark_bn254::{Bn254, Fr};
+use ark_ff::PrimeField;
+use ark_groth16::{prepare_verifying_key, Groth16, Proof, VerifyingKey};
+use ark_serialize::CanonicalDeserialize;
+use cosmwasm_std::{Addr, Binary};
+
+use crate::{crypto, error::ContractError, state::TokenId};
+
+/// Parses a compressed, canonically serialized Groth16 verifying key over BN254.
+pub fn parse_verifying_key(vk: &[u8]) -> Result<VerifyingKey<Bn254>, ContractError> {
+    VerifyingKey::<Bn254>::deserialize_compressed(vk)
+        .map_err(|_| ContractError::InvalidVerifyingKey {})
+}
+
+/// The deployment a proof is bound to: `sha256("{chain_id}/{contract}")`
+/// read as a big-endian integer and reduced into the scalar field.
+pub fn proof_domain(chain_id: &str, contract: &Addr) -> Fr {
+    Fr::from_be_bytes_mod_order(&crypto::sha256(format!("{chain_id}/{contract}").as_bytes()))
+}
+
+/// Verifies a Groth16 proof for a question circuit. The first two public
+/// inputs of every circuit are the `proof_domain` of this contract and the
+/// token id, which bind the proof to one SBT on one deployment;
+/// `public_inputs` are the remaining inputs as compressed scalar field elements.
+pub fn verify_proof(
+    vk: &[u8],
+    chain_id: &str,
+    contract: &Addr,
+    token_id: TokenId,
+    proof: &[u8],
+    public_inputs: &[Binary],
+) -> Result<bool, ContractError> {
+    let vk = parse_verifying_key(vk)?;
+    let proof = Proof::<Bn254>::deserialize_compressed(proof)
+        .map_err(|_| ContractError::InvalidProof {})?;
+
+    let mut inputs = vec![proof_domain(chain_id, contract), Fr::from(token_id)];
+    for input in public_inputs {
+        inputs.push(
+            Fr::deserialize_compressed(input.as_slice())
+                .map_err(|_| ContractError::InvalidProof {})?,
+        );
+    }
+
+    Groth16::<Bn254>::verify_proof(&prepare_verifying_key(&vk), &proof, &inputs)
+        .map_err(|_| ContractError::InvalidProof {})
+}